@@ -0,0 +1,69 @@
+//! Play back a ROSbag file recorded with a RealSense camera.
+//!
+//! This does not require a device to be connected. The center depth value of every depth frame in
+//! the recording is printed, until the end of the recording is reached.
+
+use anyhow::Result;
+use getopts::Options;
+use realsense_rust::{
+    config::Config,
+    context::Context,
+    frame::{DepthFrame, PixelKind},
+    kind::Rs2CameraInfo,
+    pipeline::InactivePipeline,
+};
+use std::{convert::TryFrom, time::Duration};
+
+/// Print usage information for this program
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} [options]", program);
+    print!("{}", opts.usage(&brief));
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let program = args[0].clone();
+    let mut opts = Options::new();
+    opts.optopt("b", "bag", "Path to the bag file", "BAG_FILE");
+    opts.optflag("h", "help", "Print this help menu");
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(f) => {
+            eprintln!("Error: {}", f);
+            std::process::exit(1);
+        }
+    };
+    if !matches.opt_present("b") || matches.opt_present("h") {
+        print_usage(&program, opts);
+        std::process::exit(0);
+    }
+
+    let bag_file = matches.opt_str("b").unwrap();
+    let context = Context::new()?;
+
+    // The recording can be inspected like any other device.
+    let device = context.add_device_from_file(&bag_file)?;
+    if let Some(name) = device.info(Rs2CameraInfo::Name) {
+        println!("Playing back recording of: {}", name.to_string_lossy());
+    }
+
+    // Stream the recording through a pipeline. Playback is not looped, so that waiting for frames
+    // times out once the end of the recording is reached.
+    let pipeline = InactivePipeline::try_from(&context)?;
+    let mut config = Config::new();
+    config.enable_device_from_file(&bag_file, false)?;
+    let mut pipeline = pipeline.start(Some(config))?;
+
+    while let Ok(frames) = pipeline.wait(Some(Duration::from_secs(1))) {
+        for frame in frames.frames_of_type::<DepthFrame>() {
+            let (col, row) = (frame.width() / 2, frame.height() / 2);
+            if let Some(PixelKind::Z16 { depth }) = frame.get(col, row) {
+                println!("Center depth: {}", depth);
+            }
+        }
+    }
+
+    pipeline.stop();
+
+    Ok(())
+}
@@ -6,3 +6,20 @@
 #![allow(missing_docs)]
 #![allow(clippy::missing_docs_in_private_items)]
 include!("../bindings/bindings.rs");
+
+// The advanced mode functions are declared in `rs_advanced_mode.h`, which is not part of the
+// headers bindgen is run on, since it declares many functions for the individual advanced mode
+// controls as well. Only the functions needed to query and toggle advanced mode are declared here.
+extern "C" {
+    pub fn rs2_toggle_advanced_mode(
+        dev: *mut rs2_device,
+        enable: ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+
+    pub fn rs2_is_enabled(
+        dev: *mut rs2_device,
+        enabled: *mut ::std::os::raw::c_int,
+        error: *mut *mut rs2_error,
+    );
+}
@@ -176,3 +176,56 @@ pub struct Rs2Roi {
     /// Bottom coordinate of the region of interest.
     pub max_y: i32,
 }
+
+impl Rs2Roi {
+    /// Construct a region of interest in pixels from fractions of a `width` by `height` frame.
+    ///
+    /// The fractions are clamped to `[0, 1]`, where `(0, 0)` is the top-left and `(1, 1)` the
+    /// bottom-right corner of the frame. The pixel bounds are inclusive, so e.g. the center 50% of
+    /// a 640 pixel wide frame spans columns 160 through 479.
+    pub fn from_normalized(
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let (min_x, max_x) = normalized_to_pixels(left, right, width);
+        let (min_y, max_y) = normalized_to_pixels(top, bottom, height);
+        Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+}
+
+/// Convert the fractional range `[start, end]` of an axis `size` pixels long to inclusive pixels.
+fn normalized_to_pixels(start: f32, end: f32, size: u32) -> (i32, i32) {
+    let last = size.saturating_sub(1) as i32;
+    let min = ((start.clamp(0.0, 1.0) * size as f32).floor() as i32).min(last);
+    let max = ((end.clamp(0.0, 1.0) * size as f32).ceil() as i32 - 1).clamp(min, last);
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_roi_is_converted_to_pixels() {
+        let roi = Rs2Roi::from_normalized(0.25, 0.25, 0.75, 0.75, 640, 480);
+        assert_eq!(
+            (roi.min_x, roi.min_y, roi.max_x, roi.max_y),
+            (160, 120, 479, 359)
+        );
+
+        let roi = Rs2Roi::from_normalized(-1.0, 0.0, 2.0, 1.0, 640, 480);
+        assert_eq!(
+            (roi.min_x, roi.min_y, roi.max_x, roi.max_y),
+            (0, 0, 639, 479)
+        );
+    }
+}
@@ -33,6 +33,9 @@ pub enum ConfigurationError {
     /// Recording to file could not be enabled for the specified device.
     #[error("Could not enable recording to file from device. Type: {0}; Reason: {1}")]
     CouldNotEnableRecordingToFile(Rs2Exception, String),
+    /// The requested format cannot be produced by streams of the requested kind.
+    #[error("Format {1:?} is not compatible with stream kind {0:?}.")]
+    IncompatibleFormat(Rs2StreamKind, Rs2Format),
 }
 
 /// Type representing the [`Pipeline`](crate::pipeline::InactivePipeline) configuration.
@@ -40,6 +43,9 @@ pub enum ConfigurationError {
 pub struct Config {
     /// A non-null pointer to the underlying librealsense2 configuration.
     config_ptr: NonNull<sys::rs2_config>,
+    /// The estimated bandwidth in bytes per second of each explicitly enabled stream, by stream
+    /// kind and index.
+    stream_bandwidths: Vec<(Rs2StreamKind, i32, u64)>,
 }
 
 impl Drop for Config {
@@ -76,6 +82,7 @@ impl Config {
 
             Self {
                 config_ptr: NonNull::new(ptr).unwrap(),
+                stream_bandwidths: Vec::new(),
             }
         }
     }
@@ -87,9 +94,8 @@ impl Config {
     ///
     /// # Arguments
     ///
-    /// It is not an error if `stream` and `format` do not match appropriately, but you may find
-    /// that the configuration will never resolve if they do not.. E.g. you cannot pass in
-    /// [`Rs2StreamKind::Color`](crate::kind::Rs2StreamKind::Color) alongside
+    /// `stream` and `format` must match appropriately (see [`Rs2Format::is_valid_for`]). E.g. you
+    /// cannot pass in [`Rs2StreamKind::Color`](crate::kind::Rs2StreamKind::Color) alongside
     /// [`Rs2Format::Z16`](crate::kind::Rs2Format::Z16). If you're unsure, pass in
     /// [`Rs2Format::Any`](crate::kind::Rs2Format::Any) and librealsense2 will determine what the
     /// most appropriate format is for a given stream.
@@ -103,9 +109,13 @@ impl Config {
     ///
     /// # Errors
     ///
+    /// Returns [`ConfigurationError::IncompatibleFormat`] if `format` cannot be produced by
+    /// streams of kind `stream`.
+    ///
     /// Returns [`ConfigurationError::CouldNotEnableStream`] if any internal exceptions occur while
-    /// making this call. Note that this does not independently check the values passed into each
-    /// of the provided arguments / attributes. If those are invalid, they will be checked when you
+    /// making this call. Note that apart from the format, this does not independently check the
+    /// values passed into each of the provided arguments / attributes. If those are invalid, they
+    /// will be checked when you
     /// call [`InactivePipeline::start`](crate::pipeline::InactivePipeline::start) or
     /// [`InactivePipeline::resolve`](crate::pipeline::InactivePipeline::resolve).
     ///
@@ -118,6 +128,10 @@ impl Config {
         format: Rs2Format,
         framerate: usize,
     ) -> Result<&mut Self, ConfigurationError> {
+        if !format.is_valid_for(stream) {
+            return Err(ConfigurationError::IncompatibleFormat(stream, format));
+        }
+
         let index: i32 = if let Some(i) = index { i as i32 } else { -1 };
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
@@ -135,9 +149,42 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotEnableStream)?;
         };
+
+        self.stream_bandwidths
+            .retain(|&(kind, i, _)| (kind, i) != (stream, index));
+        if let Some(bandwidth) = stream_bandwidth(width, height, format, framerate) {
+            self.stream_bandwidths.push((stream, index, bandwidth));
+        }
         Ok(self)
     }
 
+    /// Enable a stream in its default format, see [`Rs2StreamKind::default_format`].
+    ///
+    /// This is a shorthand for [`Config::enable_stream`] that lets librealsense2 pick the stream
+    /// index. See there for how `width`, `height` and `framerate` are interpreted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigurationError::CouldNotEnableStream`] if any internal exceptions occur while
+    /// making this call.
+    ///
+    pub fn enable_stream_default(
+        &mut self,
+        stream: Rs2StreamKind,
+        width: usize,
+        height: usize,
+        framerate: usize,
+    ) -> Result<&mut Self, ConfigurationError> {
+        self.enable_stream(
+            stream,
+            None,
+            width,
+            height,
+            stream.default_format(),
+            framerate,
+        )
+    }
+
     /// Enable all device streams explicitly.
     ///
     /// This enables all streams with the default configuration. What this means is that
@@ -283,6 +330,9 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotDisableStream)?;
         }
+
+        self.stream_bandwidths
+            .retain(|&(kind, i, _)| (kind, i) != (stream, index as i32));
         Ok(self)
     }
 
@@ -310,6 +360,9 @@ impl Config {
             );
             check_rs2_error!(err, ConfigurationError::CouldNotDisableStream)?;
         }
+
+        self.stream_bandwidths
+            .retain(|&(kind, _, _)| kind != stream);
         Ok(self)
     }
 
@@ -330,9 +383,25 @@ impl Config {
             sys::rs2_config_disable_all_streams(self.config_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, ConfigurationError::CouldNotDisableAllStreams)?;
         }
+
+        self.stream_bandwidths.clear();
         Ok(self)
     }
 
+    /// Estimate the bandwidth in bytes per second required by the explicitly enabled streams.
+    ///
+    /// Only streams enabled via [`Config::enable_stream`] with a non-zero resolution and
+    /// framerate, and a format with a fixed number of bytes per pixel (see
+    /// [`Rs2Format::bytes_per_pixel`]), are taken into account. Streams for which librealsense2
+    /// picks the resolution, framerate or format are not.
+    pub fn requested_bandwidth(&self) -> u64 {
+        self.stream_bandwidths
+            .iter()
+            .fold(0, |total, &(_, _, bandwidth)| {
+                total.saturating_add(bandwidth)
+            })
+    }
+
     /// Get the underlying low-level pointer to the configuration object.
     ///
     /// # Safety
@@ -345,3 +414,37 @@ impl Config {
         self.config_ptr
     }
 }
+
+/// Estimate the bandwidth in bytes per second of a `width` by `height` stream in `format`.
+///
+/// Returns `None` if any of the values is left for librealsense2 to pick, if the format does not
+/// have a fixed number of bytes per pixel, or if the bandwidth overflows.
+fn stream_bandwidth(
+    width: usize,
+    height: usize,
+    format: Rs2Format,
+    framerate: usize,
+) -> Option<u64> {
+    let bytes_per_pixel = format.bytes_per_pixel()?;
+    let bandwidth = width
+        .checked_mul(height)?
+        .checked_mul(bytes_per_pixel)?
+        .checked_mul(framerate)?;
+    (bandwidth != 0).then_some(bandwidth as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_bandwidth_is_estimated() {
+        assert_eq!(
+            stream_bandwidth(1280, 720, Rs2Format::Z16, 30),
+            Some(55_296_000)
+        );
+        assert_eq!(stream_bandwidth(0, 720, Rs2Format::Z16, 30), None);
+        assert_eq!(stream_bandwidth(1280, 720, Rs2Format::Mjpeg, 30), None);
+        assert_eq!(stream_bandwidth(usize::MAX, 720, Rs2Format::Z16, 30), None);
+    }
+}
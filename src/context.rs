@@ -11,7 +11,15 @@ use anyhow::Result;
 use num_traits::{FromPrimitive, ToPrimitive};
 
 use realsense_sys as sys;
-use std::{collections::HashSet, convert::From, path::Path, ptr::NonNull};
+use std::{
+    collections::HashSet,
+    convert::From,
+    ffi::CString,
+    path::{Path, PathBuf},
+    ptr::NonNull,
+    thread,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
 /// Type describing a RealSense context, used by the rest of the API.
@@ -26,11 +34,45 @@ pub struct Context {
 #[error("Could not construct the context. Type: {0}; Reason: {1}")]
 pub struct ContextConstructionError(pub Rs2Exception, pub String);
 
+/// An error type describing that the runtime librealsense2 API version is incompatible with the
+/// version this crate was compiled against.
+#[derive(Error, Debug)]
+#[error("librealsense2 API version mismatch. Compiled against: {compiled:?}; Runtime: {runtime:?}")]
+pub struct ApiVersionMismatch {
+    /// The (major, minor, patch) API version the bindings were generated for.
+    pub compiled: (u32, u32, u32),
+    /// The (major, minor, patch) API version of the librealsense2 library loaded at runtime.
+    pub runtime: (u32, u32, u32),
+}
+
 /// An error type describing failure to get the device hub from a context.
 #[derive(Error, Debug)]
 #[error("Could not get the device hub from the context. Type: {0}; Reason: {1}")]
 pub struct CouldNotGetDeviceHubError(pub Rs2Exception, pub String);
 
+/// An error type describing failure to query the devices connected to the host.
+#[derive(Error, Debug)]
+#[error("Could not query the connected devices. Type: {0}; Reason: {1}")]
+pub struct CouldNotQueryDevicesError(pub Rs2Exception, pub String);
+
+/// An error type describing that no devices are connected to the host.
+#[derive(Error, Debug)]
+#[error("No devices are connected.")]
+pub struct NoDevicesConnectedError;
+
+/// An error type describing that a device did not appear or disappear within the given timeout.
+#[derive(Error, Debug)]
+#[error("Timed out while waiting for the set of connected devices to change.")]
+pub struct DeviceWaitTimedOutError;
+
+/// An error type describing that a device cannot be identified, because it has no serial number.
+#[derive(Error, Debug)]
+#[error("The device has no serial number.")]
+pub struct NoSerialNumberError;
+
+/// The interval at which the connected devices are polled while waiting for them to change.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// An error type describing failure to add a device from a file.
 #[derive(Error, Debug)]
 #[error("Could not add a device from file. Type: {0}; Reason: {1}")]
@@ -41,6 +83,126 @@ pub struct CouldNotAddDeviceError(pub Rs2Exception, pub String);
 #[error("Could not remove device from file. Type: {0}; Reason: {1}")]
 pub struct CouldNotRemoveDeviceError(pub Rs2Exception, pub String);
 
+/// How librealsense2 converts the formats streamed by devices, see
+/// [`ContextBuilder::format_conversion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatConversion {
+    /// Provide all conversions, e.g. from YUYV to RGB8. This is the default.
+    Full,
+    /// Provide mostly the formats of the device (e.g. no RGB8), but split interleaved formats
+    /// (e.g. Y8I into two Y8 streams).
+    Basic,
+    /// Provide the formats of the device as they are.
+    Raw,
+}
+
+impl FormatConversion {
+    /// Get the name of the conversion in the context settings.
+    fn as_setting(self) -> &'static str {
+        match self {
+            FormatConversion::Full => "full",
+            FormatConversion::Basic => "basic",
+            FormatConversion::Raw => "raw",
+        }
+    }
+}
+
+/// Builder for a [`Context`] with non-default settings, see [`Context::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+    /// Whether to ignore the settings of the librealsense2 configuration file.
+    ignore_configuration_file: bool,
+    /// How the formats streamed by devices are converted, if not the default.
+    format_conversion: Option<FormatConversion>,
+    /// The interval at which option changes are polled, if not the default.
+    options_update_interval: Option<Duration>,
+    /// Recordings to add to the context as playback devices.
+    playback_files: Vec<PathBuf>,
+}
+
+impl ContextBuilder {
+    /// Ignore the settings of the librealsense2 configuration file, so that the context behaves
+    /// the same regardless of the host it runs on.
+    pub fn ignore_configuration_file(mut self) -> Self {
+        self.ignore_configuration_file = true;
+        self
+    }
+
+    /// Set how librealsense2 converts the formats streamed by devices.
+    pub fn format_conversion(mut self, conversion: FormatConversion) -> Self {
+        self.format_conversion = Some(conversion);
+        self
+    }
+
+    /// Set the interval at which librealsense2 polls devices for option changes.
+    pub fn options_update_interval(mut self, interval: Duration) -> Self {
+        self.options_update_interval = Some(interval);
+        self
+    }
+
+    /// Add a recording (e.g. a ROSbag) to the context as a playback device, before any devices
+    /// are enumerated.
+    ///
+    /// See [`Context::add_device_from_file`]. Using only the playback devices allows for tests
+    /// that do not depend on the hardware attached to the host.
+    pub fn playback_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.playback_files.push(file.as_ref().to_path_buf());
+        self
+    }
+
+    /// Get the settings of the context as JSON, as passed to librealsense2.
+    fn settings_json(&self) -> String {
+        let mut settings = Vec::new();
+        if self.ignore_configuration_file {
+            settings.push("\"inherit\":false".to_owned());
+        }
+        if let Some(conversion) = self.format_conversion {
+            settings.push(format!(
+                "\"format-conversion\":\"{}\"",
+                conversion.as_setting()
+            ));
+        }
+        if let Some(interval) = self.options_update_interval {
+            settings.push(format!(
+                "\"options-update-interval\":{}",
+                interval.as_millis().min(u32::MAX as u128)
+            ));
+        }
+        format!("{{{}}}", settings.join(","))
+    }
+
+    /// Construct the context.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContextConstructionError`] if the context cannot be created, e.g. because the
+    /// settings are not supported by the librealsense2 library loaded at runtime.
+    ///
+    /// Returns any error of [`Context::add_device_from_file`] if a recording cannot be added.
+    ///
+    pub fn build(self) -> Result<Context> {
+        let settings = CString::new(self.settings_json()).unwrap();
+        let context = unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let ptr = sys::rs2_create_context_ex(
+                sys::RS2_API_VERSION as i32,
+                settings.as_ptr(),
+                &mut err,
+            );
+            check_rs2_error!(err, ContextConstructionError)?;
+
+            Context {
+                context_ptr: NonNull::new(ptr).unwrap(),
+            }
+        };
+
+        for file in &self.playback_files {
+            context.add_device_from_file(file)?;
+        }
+        Ok(context)
+    }
+}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe { sys::rs2_delete_context(self.context_ptr.as_ptr()) }
@@ -68,6 +230,39 @@ impl Context {
         }
     }
 
+    /// Start building a context with non-default settings.
+    ///
+    /// librealsense2 does not provide alternative backends for device enumeration, but recordings
+    /// can be added as playback devices (see [`ContextBuilder::playback_file`]) and the settings
+    /// that would otherwise be read from the configuration file of the host can be overridden.
+    ///
+    pub fn builder() -> ContextBuilder {
+        ContextBuilder::default()
+    }
+
+    /// Construct a new context, after checking that the runtime librealsense2 API version is
+    /// compatible with the version the bindings were generated for.
+    ///
+    /// The versions are compatible if the major versions are the same and the runtime minor
+    /// version is at least the compiled minor version. Checking this up front turns an ABI
+    /// mismatch into a clear error, rather than undefined behaviour later on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiVersionMismatch`] if the runtime API version is incompatible.
+    ///
+    /// Returns [`ContextConstructionError`] if the context cannot be created.
+    ///
+    pub fn with_version_check() -> Result<Self> {
+        let compiled = decode_api_version(sys::RS2_API_VERSION as i32);
+        let runtime = api_version();
+
+        if !is_api_compatible(compiled, runtime) {
+            return Err(ApiVersionMismatch { compiled, runtime }.into());
+        }
+        Ok(Self::new()?)
+    }
+
     /// Creates a device hub from the context.
     ///
     /// # Errors
@@ -85,15 +280,14 @@ impl Context {
     }
 
     /// Get a list of devices that are already connected to the host.
+    ///
+    /// Only devices belonging to one of the product lines in `product_mask` are returned, e.g.
+    /// passing a set containing only [`Rs2ProductLine::D400`] ignores any connected tracking
+    /// cameras. The product lines are OR-ed together into a single mask for
+    /// `rs2_query_devices_ex`. An empty `product_mask` is treated as [`Rs2ProductLine::Any`], and
+    /// returns all connected devices.
     pub fn query_devices(&self, product_mask: HashSet<Rs2ProductLine>) -> Vec<Device> {
-        // TODO/TEST: Make sure that an empty mask (therefore giving no filter) gives
-        // us _all_ devices, not _no_ devices.
-
-        let mask = if product_mask.is_empty() {
-            Rs2ProductLine::Any.to_i32().unwrap()
-        } else {
-            product_mask.iter().fold(0, |k, v| k | v.to_u32().unwrap()) as i32
-        };
+        let mask = product_mask_bits(&product_mask);
 
         let mut devices = Vec::new();
         unsafe {
@@ -132,11 +326,200 @@ impl Context {
         devices
     }
 
+    /// Get a list of connected devices that are in recovery (DFU) mode.
+    ///
+    /// Devices end up in recovery mode e.g. after a failed firmware update, and can then only be
+    /// brought back by updating their firmware. See [`Device::is_in_recovery_mode`].
+    pub fn query_recovery_devices(&self) -> Vec<Device> {
+        self.query_devices(HashSet::new())
+            .into_iter()
+            .filter(Device::is_in_recovery_mode)
+            .collect()
+    }
+
+    /// Get the number of devices that are currently connected to the host.
+    ///
+    /// Returns zero if an error occurs while querying the devices.
+    pub fn device_count(&self) -> usize {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let device_list_ptr = sys::rs2_query_devices(self.context_ptr.as_ptr(), &mut err);
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return 0;
+            }
+
+            let len = sys::rs2_get_device_count(device_list_ptr, &mut err);
+            sys::rs2_delete_device_list(device_list_ptr);
+
+            if err.as_ref().is_none() {
+                len as usize
+            } else {
+                sys::rs2_free_error(err);
+                0
+            }
+        }
+    }
+
+    /// Get the first device that is connected to the host.
+    ///
+    /// This is mostly useful for applications that only ever expect a single device to be
+    /// connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoDevicesConnectedError`] if no devices are connected.
+    ///
+    /// Returns [`CouldNotQueryDevicesError`] if the connected devices cannot be queried.
+    ///
+    /// Returns [`DeviceConstructionError`](crate::device::DeviceConstructionError) if the device
+    /// cannot be created.
+    ///
+    pub fn first_device(&self) -> Result<Device> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let device_list_ptr = sys::rs2_query_devices(self.context_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, CouldNotQueryDevicesError)?;
+
+            let device_list = NonNull::new(device_list_ptr).unwrap();
+
+            let len = sys::rs2_get_device_count(device_list.as_ptr(), &mut err);
+            if err.as_ref().is_some() {
+                sys::rs2_delete_device_list(device_list.as_ptr());
+            }
+            check_rs2_error!(err, CouldNotQueryDevicesError)?;
+
+            if len == 0 {
+                sys::rs2_delete_device_list(device_list.as_ptr());
+                return Err(NoDevicesConnectedError.into());
+            }
+
+            let device = Device::try_create(&device_list, 0);
+            sys::rs2_delete_device_list(device_list.as_ptr());
+            Ok(device?)
+        }
+    }
+
+    /// Wait for a device to be connected to the host, blocking the calling thread.
+    ///
+    /// Returns the first connected device as soon as there is one, which is immediately if a
+    /// device is already connected. The connected devices are polled at a short interval, so
+    /// unlike the device hub this does not rely on callbacks from librealsense2 threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceWaitTimedOutError`] if no device is connected within `timeout`.
+    ///
+    /// Returns any other error of [`Context::first_device`], e.g. if the connected devices cannot
+    /// be queried.
+    ///
+    pub fn wait_for_device(&self, timeout: Duration) -> Result<Device> {
+        let start = Instant::now();
+        loop {
+            match self.first_device() {
+                Ok(device) => return Ok(device),
+                Err(e) if e.is::<NoDevicesConnectedError>() => {}
+                Err(e) => return Err(e),
+            }
+            sleep_until_next_poll(start, timeout)?;
+        }
+    }
+
+    /// Wait for a device to be disconnected from the host, blocking the calling thread.
+    ///
+    /// The device is identified by its serial number, and considered removed as soon as none of
+    /// the connected devices has the same serial number. Like [`Context::wait_for_device`], this
+    /// polls the connected devices at a short interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoSerialNumberError`] if the device has no serial number.
+    ///
+    /// Returns [`CouldNotQueryDevicesError`] if the connected devices cannot be queried.
+    ///
+    /// Returns [`DeviceWaitTimedOutError`] if the device is still connected after `timeout`.
+    ///
+    pub fn wait_for_device_removal(&self, device: &Device, timeout: Duration) -> Result<()> {
+        let serial_number = device.serial_number().ok_or(NoSerialNumberError)?;
+        let start = Instant::now();
+        while self.device_by_serial_number(&serial_number)?.is_some() {
+            sleep_until_next_poll(start, timeout)?;
+        }
+        Ok(())
+    }
+
+    /// Reset a device and wait for it to reconnect, blocking the calling thread.
+    ///
+    /// The device is reset with [`Device::hardware_reset`], which disconnects it from the host.
+    /// The device is identified by its serial number, so this first waits for it to disappear, and
+    /// then for a device with the same serial number to appear again. The `timeout` applies to
+    /// both steps together.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoSerialNumberError`] if the device has no serial number. The device is not
+    /// reset in that case.
+    ///
+    /// Returns [`CouldNotQueryDevicesError`] if the connected devices cannot be queried.
+    ///
+    /// Returns [`DeviceWaitTimedOutError`] if the device has not reconnected within `timeout`.
+    ///
+    pub fn hardware_reset_and_wait(&self, device: Device, timeout: Duration) -> Result<Device> {
+        let serial_number = device.serial_number().ok_or(NoSerialNumberError)?;
+        let start = Instant::now();
+        device.hardware_reset();
+
+        while self.device_by_serial_number(&serial_number)?.is_some() {
+            sleep_until_next_poll(start, timeout)?;
+        }
+
+        loop {
+            if let Some(device) = self.device_by_serial_number(&serial_number)? {
+                return Ok(device);
+            }
+            sleep_until_next_poll(start, timeout)?;
+        }
+    }
+
+    /// Find the connected device with the given serial number.
+    ///
+    /// Unlike [`Context::query_devices`], a failure to query the connected devices is reported
+    /// rather than treated as if no devices were connected. Devices that cannot be created are
+    /// skipped.
+    fn device_by_serial_number(
+        &self,
+        serial_number: &str,
+    ) -> Result<Option<Device>, CouldNotQueryDevicesError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let device_list_ptr = sys::rs2_query_devices(self.context_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, CouldNotQueryDevicesError)?;
+
+            let device_list = NonNull::new(device_list_ptr).unwrap();
+
+            let len = sys::rs2_get_device_count(device_list.as_ptr(), &mut err);
+            if err.as_ref().is_some() {
+                sys::rs2_delete_device_list(device_list.as_ptr());
+            }
+            check_rs2_error!(err, CouldNotQueryDevicesError)?;
+
+            let device = (0..len)
+                .filter_map(|i| Device::try_create(&device_list, i).ok())
+                .find(|device| device.serial_number().as_deref() == Some(serial_number));
+
+            sys::rs2_delete_device_list(device_list.as_ptr());
+            Ok(device)
+        }
+    }
+
     /// Create a new device and add it to the context.
     ///
     /// This adds a "device" at a particular file on the system to the RealSense context. Returns a
     /// handle to the device, or an error if this call fails.
     ///
+    /// See [`Context::add_device_from_file`].
+    ///
     /// # Errors
     ///
     /// Returns [`NulError`](std::ffi::NulError) if the provided file path cannot be cleanly
@@ -147,6 +530,28 @@ impl Context {
     /// Returns [`CouldNotAddDeviceError`] if the device cannot be added.
     ///
     pub fn add_device<P>(&mut self, file: P) -> Result<Device>
+    where
+        P: AsRef<Path>,
+    {
+        self.add_device_from_file(file)
+    }
+
+    /// Add a playback device backed by a recorded file (e.g. a ROSbag) to the context.
+    ///
+    /// The returned device behaves like a connected device, which makes it possible to process
+    /// recordings without any hardware attached (e.g. in CI). Its sensors and streams are those
+    /// found in the recording. To stream the recording through a pipeline instead, see
+    /// [`Config::enable_device_from_file`](crate::config::Config::enable_device_from_file).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NulError`](std::ffi::NulError) if the provided file path cannot be cleanly
+    /// represented as a [`CString`](std::ffi::CString).
+    ///
+    /// Returns [`CouldNotAddDeviceError`] if the device cannot be added, e.g. if the file does not
+    /// exist or is not a valid recording.
+    ///
+    pub fn add_device_from_file<P>(&self, file: P) -> Result<Device>
     where
         P: AsRef<Path>,
     {
@@ -196,3 +601,123 @@ impl Context {
         self.context_ptr
     }
 }
+
+/// Get the (major, minor, patch) API version of the librealsense2 library loaded at runtime.
+///
+/// Returns `(0, 0, 0)` if the version cannot be obtained.
+pub fn api_version() -> (u32, u32, u32) {
+    unsafe {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        let version = sys::rs2_get_api_version(&mut err);
+
+        if err.as_ref().is_none() {
+            decode_api_version(version)
+        } else {
+            sys::rs2_free_error(err);
+            (0, 0, 0)
+        }
+    }
+}
+
+/// Split an API version as encoded by librealsense2 (`major * 10000 + minor * 100 + patch`) into
+/// its components.
+fn decode_api_version(version: i32) -> (u32, u32, u32) {
+    let version = version.max(0) as u32;
+    (version / 10000, (version / 100) % 100, version % 100)
+}
+
+/// Predicate for checking if the runtime API version can be used with the compiled API version.
+fn is_api_compatible(compiled: (u32, u32, u32), runtime: (u32, u32, u32)) -> bool {
+    compiled.0 == runtime.0 && runtime.1 >= compiled.1
+}
+
+/// Combine a set of product lines into a single mask understood by `rs2_query_devices_ex`.
+///
+/// An empty set is treated as [`Rs2ProductLine::Any`].
+fn product_mask_bits(product_mask: &HashSet<Rs2ProductLine>) -> i32 {
+    if product_mask.is_empty() {
+        Rs2ProductLine::Any.to_i32().unwrap()
+    } else {
+        product_mask.iter().fold(0, |k, v| k | v.to_u32().unwrap()) as i32
+    }
+}
+
+/// Sleep until the connected devices should be polled again.
+///
+/// Returns [`DeviceWaitTimedOutError`] instead if `timeout` has elapsed since `start`.
+fn sleep_until_next_poll(start: Instant, timeout: Duration) -> Result<(), DeviceWaitTimedOutError> {
+    let remaining = timeout
+        .checked_sub(start.elapsed())
+        .filter(|remaining| !remaining.is_zero())
+        .ok_or(DeviceWaitTimedOutError)?;
+    thread::sleep(remaining.min(DEVICE_POLL_INTERVAL));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_settings_are_serialized() {
+        assert_eq!(Context::builder().settings_json(), "{}");
+
+        let builder = Context::builder()
+            .ignore_configuration_file()
+            .format_conversion(FormatConversion::Raw)
+            .options_update_interval(Duration::from_millis(250));
+        assert_eq!(
+            builder.settings_json(),
+            r#"{"inherit":false,"format-conversion":"raw","options-update-interval":250}"#
+        );
+    }
+
+    #[test]
+    fn polling_stops_after_timeout() {
+        let start = Instant::now();
+        assert!(sleep_until_next_poll(start, Duration::ZERO).is_err());
+        assert!(sleep_until_next_poll(start, Duration::from_secs(60)).is_ok());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn api_version_is_decoded() {
+        assert_eq!(decode_api_version(25501), (2, 55, 1));
+        assert_eq!(
+            decode_api_version(sys::RS2_API_VERSION as i32),
+            (
+                sys::RS2_API_MAJOR_VERSION,
+                sys::RS2_API_MINOR_VERSION,
+                sys::RS2_API_PATCH_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn api_compatibility() {
+        assert!(is_api_compatible((2, 55, 1), (2, 55, 1)));
+        assert!(is_api_compatible((2, 55, 1), (2, 56, 0)));
+        assert!(!is_api_compatible((2, 55, 1), (2, 54, 9)));
+        assert!(!is_api_compatible((2, 55, 1), (3, 55, 1)));
+    }
+
+    #[test]
+    fn empty_product_mask_is_any() {
+        assert_eq!(
+            product_mask_bits(&HashSet::new()),
+            sys::RS2_PRODUCT_LINE_ANY as i32
+        );
+    }
+
+    #[test]
+    fn product_mask_is_ored() {
+        let mask: HashSet<_> = [Rs2ProductLine::D400, Rs2ProductLine::L500]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(
+            product_mask_bits(&mask),
+            (sys::RS2_PRODUCT_LINE_D400 | sys::RS2_PRODUCT_LINE_L500) as i32
+        );
+    }
+}
@@ -7,17 +7,21 @@
 
 use crate::{
     check_rs2_error,
-    kind::{Rs2CameraInfo, Rs2Exception},
-    sensor::Sensor,
+    config::Config,
+    kind::{OptionSetError, Rs2CameraInfo, Rs2Exception, Rs2Extension, Rs2Option},
+    sensor::{Sensor, SensorConstructionError},
 };
 use anyhow::Result;
 #[allow(unused_imports)]
 use num_traits::FromPrimitive;
 use realsense_sys as sys;
 use std::{
+    cell::OnceCell,
+    collections::HashMap,
     convert::{From, TryInto},
     ffi::CStr,
     os::raw::c_int,
+    panic::{self, AssertUnwindSafe},
     ptr::NonNull,
 };
 use thiserror::Error;
@@ -33,6 +37,97 @@ pub enum DeviceConstructionError {
     CouldNotGetDeviceFromDeviceList(Rs2Exception, String),
 }
 
+/// Enumeration of possible errors that can occur when querying the sensors of a device.
+#[derive(Error, Debug)]
+pub enum DeviceError {
+    /// Could not query the sensor list of the device, e.g. because it was disconnected.
+    #[error("Could not query sensors of device. Type: {0}; Reason: {1}")]
+    CouldNotQuerySensors(Rs2Exception, String),
+    /// Could not get the number of sensors in the sensor list.
+    #[error("Could not get sensor count. Type: {0}; Reason: {1}")]
+    CouldNotGetSensorCount(Rs2Exception, String),
+    /// Could not get a sensor from the sensor list.
+    #[error("Could not get sensor from sensor list. Type: {0}; Reason: {1}")]
+    CouldNotGetSensorFromList(Rs2Exception, String),
+}
+
+/// Enumeration of possible errors that can occur when loading or serializing JSON presets.
+#[derive(Error, Debug)]
+pub enum PresetJsonError {
+    /// The device does not support advanced mode, and thus JSON presets.
+    #[error("Device does not support advanced mode.")]
+    AdvancedModeNotSupported,
+    /// The JSON preset is too large to be passed to librealsense2.
+    #[error("JSON preset is too large.")]
+    JsonTooLarge,
+    /// Could not load the JSON preset onto the device.
+    #[error("Could not load JSON preset. Type: {0}; Reason: {1}")]
+    CouldNotLoadJson(Rs2Exception, String),
+    /// Could not serialize the current device settings to a JSON preset.
+    #[error("Could not serialize JSON preset. Type: {0}; Reason: {1}")]
+    CouldNotSerializeJson(Rs2Exception, String),
+}
+
+/// Enumeration of possible errors that can occur when querying or toggling advanced mode.
+#[derive(Error, Debug)]
+pub enum AdvancedModeError {
+    /// The device does not support advanced mode.
+    #[error("Device does not support advanced mode.")]
+    AdvancedModeNotSupported,
+    /// Could not check if advanced mode is enabled.
+    #[error("Could not check if advanced mode is enabled. Type: {0}; Reason: {1}")]
+    CouldNotGetAdvancedMode(Rs2Exception, String),
+    /// Could not enable or disable advanced mode.
+    #[error("Could not toggle advanced mode. Type: {0}; Reason: {1}")]
+    CouldNotSetAdvancedMode(Rs2Exception, String),
+}
+
+/// Enumeration of possible errors that can occur when reading or writing the calibration table.
+#[derive(Error, Debug)]
+pub enum CalibrationTableError {
+    /// The device does not support auto-calibration, and thus calibration table access.
+    #[error("Device does not support auto-calibration.")]
+    AutoCalibrationNotSupported,
+    /// The calibration table is too large to be passed to librealsense2.
+    #[error("Calibration table is too large.")]
+    TableTooLarge,
+    /// Could not read the calibration table from the device.
+    #[error("Could not get calibration table. Type: {0}; Reason: {1}")]
+    CouldNotGetCalibrationTable(Rs2Exception, String),
+    /// Could not write the calibration table to the device.
+    #[error("Could not set calibration table. Type: {0}; Reason: {1}")]
+    CouldNotSetCalibrationTable(Rs2Exception, String),
+    /// The JSON calibration configuration is too large to be passed to librealsense2.
+    #[error("Calibration configuration is too large.")]
+    ConfigTooLarge,
+    /// Could not run on-chip calibration on the device.
+    #[error("Could not run on-chip calibration. Type: {0}; Reason: {1}")]
+    CouldNotRunOnChipCalibration(Rs2Exception, String),
+}
+
+/// Enumeration of possible errors that can occur when updating the firmware of a device.
+#[derive(Error, Debug)]
+pub enum FirmwareUpdateError {
+    /// The device does not support firmware updates.
+    #[error("Device does not support firmware updates.")]
+    UpdateNotSupported,
+    /// Could not switch the device to its update (recovery) state.
+    #[error("Could not enter update state. Type: {0}; Reason: {1}")]
+    CouldNotEnterUpdateState(Rs2Exception, String),
+    /// The firmware image is too large to be passed to librealsense2.
+    #[error("Firmware image is too large.")]
+    ImageTooLarge,
+    /// Could not write the firmware image to the device.
+    #[error("Could not update firmware. Type: {0}; Reason: {1}")]
+    CouldNotUpdateFirmware(Rs2Exception, String),
+}
+
+/// The bandwidth in bytes per second that can roughly be achieved over a USB 2 connection.
+const USB2_BANDWIDTH: u64 = 40_000_000;
+
+/// The timeout for on-chip calibration recommended by librealsense2.
+const ON_CHIP_CALIBRATION_TIMEOUT_MS: c_int = 5000;
+
 /// A type representing a RealSense device.
 ///
 /// A device in librealsense2 corresponds to a physical unit that connects to your computer
@@ -45,10 +140,14 @@ pub enum DeviceConstructionError {
 pub struct Device {
     /// A non-null pointer to the underlying librealsense device
     device_ptr: NonNull<sys::rs2_device>,
+    /// The sensors of the device, enumerated on first use by [`Device::sensor_handles`].
+    sensor_cache: OnceCell<Vec<Sensor>>,
 }
 
 impl Drop for Device {
     fn drop(&mut self) {
+        // Release the cached sensors before the device they belong to.
+        self.sensor_cache.take();
         unsafe {
             sys::rs2_delete_device(self.device_ptr.as_ptr());
         }
@@ -63,7 +162,10 @@ impl From<NonNull<sys::rs2_device>> for Device {
     /// Constructs a device from a pointer to an `rs2_device` type from the C-FFI.
     ///
     fn from(device_ptr: NonNull<sys::rs2_device>) -> Self {
-        Device { device_ptr }
+        Device {
+            device_ptr,
+            sensor_cache: OnceCell::new(),
+        }
     }
 }
 
@@ -96,7 +198,17 @@ impl Device {
     /// Gets a list of sensors associated with the device.
     ///
     /// Returns a vector of zero size if any error occurs while trying to read the sensor list.
-    /// This can occur if the physical device is disconnected before this call is made.
+    /// This can occur if the physical device is disconnected before this call is made. Use
+    /// [`Device::try_sensors`] to tell such an error apart from a device without sensors.
+    ///
+    /// The sensors are returned in the order librealsense2 enumerates them, which is fixed for a
+    /// given device (and for recordings of it). Sensors that cannot be acquired are skipped, so
+    /// positions in this list only match librealsense2's sensor indices if no sensor was skipped;
+    /// [`Device::try_sensors`] guarantees that they do.
+    ///
+    /// The sensor list is queried from librealsense2 on every call. Prefer
+    /// [`Device::sensor_handles`] when the sensors are accessed repeatedly, e.g. to poll options
+    /// from a control loop.
     ///
     pub fn sensors(&self) -> Vec<Sensor> {
         unsafe {
@@ -136,6 +248,154 @@ impl Device {
         }
     }
 
+    /// Gets a list of sensors associated with the device, reporting any error that occurs.
+    ///
+    /// Unlike [`Device::sensors`], this distinguishes a failure to read the sensor list (e.g.
+    /// because the physical device was disconnected) from a device that has no sensors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceError::CouldNotQuerySensors`] if the sensor list cannot be queried.
+    ///
+    /// Returns [`DeviceError::CouldNotGetSensorCount`] if the length of the sensor list cannot be
+    /// determined.
+    ///
+    /// Returns [`DeviceError::CouldNotGetSensorFromList`] if any sensor in the list cannot be
+    /// acquired.
+    ///
+    /// On success, the position of each sensor in the returned list is its index in
+    /// librealsense2's sensor list, which is stable across runs for a given device.
+    ///
+    pub fn try_sensors(&self) -> Result<Vec<Sensor>, DeviceError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let sensor_list_ptr = sys::rs2_query_sensors(self.device_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, DeviceError::CouldNotQuerySensors)?;
+
+            let nonnull_sensor_list = NonNull::new(sensor_list_ptr).unwrap();
+
+            let sensors = (|| {
+                let len = sys::rs2_get_sensors_count(nonnull_sensor_list.as_ptr(), &mut err);
+                check_rs2_error!(err, DeviceError::CouldNotGetSensorCount)?;
+
+                (0..len)
+                    .map(|i| {
+                        Sensor::try_create(&nonnull_sensor_list, i).map_err(|e| match e {
+                            SensorConstructionError::CouldNotGetSensorFromList(kind, reason) => {
+                                DeviceError::CouldNotGetSensorFromList(kind, reason)
+                            }
+                        })
+                    })
+                    .collect()
+            })();
+
+            sys::rs2_delete_sensor_list(nonnull_sensor_list.as_ptr());
+            sensors
+        }
+    }
+
+    /// Gets the sensors associated with the device, enumerating them only once.
+    ///
+    /// The sensor list is queried on the first call and cached for the lifetime of the device, so
+    /// subsequent calls are free. If the sensor list could not be read (e.g. because the device
+    /// was disconnected), the empty list is cached as well.
+    ///
+    /// The cache is never invalidated on its own. Call [`Device::refresh_sensors`] to enumerate
+    /// the sensors again, e.g. after the device has been reconnected.
+    ///
+    pub fn sensor_handles(&self) -> &[Sensor] {
+        self.sensor_cache.get_or_init(|| self.sensors())
+    }
+
+    /// Discards the cached sensors and enumerates them again.
+    ///
+    /// Returns the freshly enumerated sensors. See [`Device::sensor_handles`].
+    ///
+    pub fn refresh_sensors(&mut self) -> &[Sensor] {
+        self.sensor_cache.take();
+        self.sensor_handles()
+    }
+
+    /// Gets the sensor of the device with the given name, e.g. `"Stereo Module"`, `"RGB Camera"`
+    /// or `"Motion Module"`.
+    ///
+    /// The name is compared against [`Rs2CameraInfo::Name`] of each sensor, which is how sensors
+    /// are usually identified across product lines. Returns `None` if no sensor has that name, or
+    /// if the sensor list cannot be read (see [`Device::sensors`]).
+    ///
+    pub fn sensor_by_name(&self, name: &str) -> Option<Sensor> {
+        self.sensors()
+            .into_iter()
+            .find(|sensor| sensor.name().as_deref() == Some(name))
+    }
+
+    /// Gets the index of the sensor of the device with the given name, e.g. `"Stereo Module"`.
+    ///
+    /// The index is the sensor's position in librealsense2's sensor list, and therefore in the
+    /// list returned by [`Device::try_sensors`]. It is stable across runs for a given device, so it
+    /// can be stored to address the sensor later. Returns `None` if no sensor has that name, or if
+    /// the sensor list cannot be read.
+    ///
+    pub fn sensor_index_by_name(&self, name: &str) -> Option<usize> {
+        self.try_sensors()
+            .ok()?
+            .iter()
+            .position(|sensor| sensor.name().as_deref() == Some(name))
+    }
+
+    /// Enables or disables global time on every sensor of the device that supports it.
+    ///
+    /// With global time enabled, frame timestamps are converted to the host clock (see
+    /// [`Rs2TimestampDomain::GlobalTime`](crate::kind::Rs2TimestampDomain::GlobalTime)), which
+    /// lets frames from several devices be compared with each other. This sets
+    /// [`Rs2Option::GlobalTimeEnabled`] on each sensor that supports it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionNotSupported`] if no sensor of the device supports global
+    /// time.
+    ///
+    /// Returns the first [`OptionSetError`] raised while setting the option on a sensor. The
+    /// option is not set on the remaining sensors in that case.
+    ///
+    pub fn enable_global_time(&self, enabled: bool) -> Result<(), OptionSetError> {
+        let mut sensors: Vec<Sensor> = self
+            .sensors()
+            .into_iter()
+            .filter(|sensor| sensor.supports_option(Rs2Option::GlobalTimeEnabled))
+            .collect();
+
+        if sensors.is_empty() {
+            return Err(OptionSetError::OptionNotSupported);
+        }
+
+        let value = if enabled { 1.0 } else { 0.0 };
+        for sensor in &mut sensors {
+            sensor.set_option(Rs2Option::GlobalTimeEnabled, value)?;
+        }
+        Ok(())
+    }
+
+    /// Predicate for whether global time is enabled on every sensor of the device that supports
+    /// it.
+    ///
+    /// Returns `None` if no sensor of the device supports global time, or if the option could not
+    /// be read. See [`Device::enable_global_time`].
+    ///
+    pub fn is_global_time_enabled(&self) -> Option<bool> {
+        let values: Option<Vec<f32>> = self
+            .sensors()
+            .iter()
+            .filter(|sensor| sensor.supports_option(Rs2Option::GlobalTimeEnabled))
+            .map(|sensor| sensor.get_option(Rs2Option::GlobalTimeEnabled))
+            .collect();
+
+        match values {
+            Some(values) if !values.is_empty() => Some(values.iter().all(|value| *value != 0.0)),
+            _ => None,
+        }
+    }
+
     /// Takes ownership of the device and forces a hardware reset on the device.
     ///
     /// Ownership of the device is taken as the underlying state can no longer be safely retained
@@ -156,6 +416,42 @@ impl Device {
         }
     }
 
+    /// Predicate for whether the device is in recovery (DFU) mode.
+    ///
+    /// Devices in recovery mode, e.g. after a failed firmware update, only support firmware
+    /// updates. Their [DFU device path](Rs2CameraInfo::DfuDevicePath) identifies them.
+    ///
+    pub fn is_in_recovery_mode(&self) -> bool {
+        self.is_extendable_to(Rs2Extension::UpdateDevice)
+    }
+
+    /// Takes ownership of the device and switches it to its update (recovery) state.
+    ///
+    /// The device disconnects and reappears in recovery mode, where it can be found via
+    /// [`Context::query_recovery_devices`](crate::context::Context::query_recovery_devices).
+    /// Ownership of the device is taken as the underlying state can no longer be safely retained
+    /// after the switch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FirmwareUpdateError::UpdateNotSupported`] if the device does not support
+    /// firmware updates.
+    ///
+    /// Returns [`FirmwareUpdateError::CouldNotEnterUpdateState`] if the device could not be
+    /// switched to its update state.
+    ///
+    pub fn enter_update_state(self) -> Result<(), FirmwareUpdateError> {
+        if !self.is_extendable_to(Rs2Extension::Updatable) {
+            return Err(FirmwareUpdateError::UpdateNotSupported);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_enter_update_state(self.device_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, FirmwareUpdateError::CouldNotEnterUpdateState)
+        }
+    }
+
     /// Gets the value associated with the provided camera info key from the device.
     ///
     /// Returns some information value associated with the camera info key if the `camera_info` is
@@ -208,6 +504,465 @@ impl Device {
         }
     }
 
+    /// Gets every camera info value the device supports.
+    ///
+    /// Values that are not valid UTF-8 are converted lossily.
+    pub fn info_all(&self) -> HashMap<Rs2CameraInfo, String> {
+        (0..sys::rs2_camera_info_RS2_CAMERA_INFO_COUNT as i32)
+            .filter_map(Rs2CameraInfo::from_i32)
+            .filter_map(|camera_info| {
+                self.info(camera_info)
+                    .map(|val| (camera_info, val.to_string_lossy().into_owned()))
+            })
+            .collect()
+    }
+
+    /// Gets the serial number of the device.
+    ///
+    /// Returns `None` if the device does not provide [`Rs2CameraInfo::SerialNumber`].
+    pub fn serial_number(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::SerialNumber)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the serial number of the ASIC of the device.
+    ///
+    /// This differs from the [serial number](Device::serial_number) of the device, and is e.g.
+    /// used to correlate a device with support requests. Returns `None` if the device does not
+    /// provide [`Rs2CameraInfo::AsicSerialNumber`].
+    pub fn asic_serial_number(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::AsicSerialNumber)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the firmware update ID of the device.
+    ///
+    /// Returns `None` if the device does not provide [`Rs2CameraInfo::FirmwareUpdateId`].
+    pub fn firmware_update_id(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::FirmwareUpdateId)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the name of the device.
+    ///
+    /// Returns `None` if the device does not provide [`Rs2CameraInfo::Name`].
+    pub fn name(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::Name)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the physical port of the device.
+    ///
+    /// Returns `None` if the device does not provide [`Rs2CameraInfo::PhysicalPort`].
+    pub fn physical_port(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::PhysicalPort)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the USB type descriptor (e.g. "3.2") of the device.
+    ///
+    /// Returns `None` if the device does not provide [`Rs2CameraInfo::UsbTypeDescriptor`].
+    pub fn usb_type(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::UsbTypeDescriptor)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Predicate for whether the device is connected via USB 3.
+    ///
+    /// Returns false if the device does not provide its [USB type](Device::usb_type), e.g. because
+    /// it is played back from a file.
+    pub fn is_usb3(&self) -> bool {
+        self.usb_type()
+            .is_some_and(|usb_type| usb_type.starts_with('3'))
+    }
+
+    /// Predicate for whether the streams requested by `config` likely exceed the bandwidth of the
+    /// device's USB connection.
+    ///
+    /// This is only the case for devices connected via USB 2, which support fewer (and smaller)
+    /// stream profiles than via USB 3. It is useful to diagnose why a configuration cannot be
+    /// resolved. The bandwidth of the configuration is only an estimate, see
+    /// [`Config::requested_bandwidth`].
+    pub fn exceeds_usb2_bandwidth(&self, config: &Config) -> bool {
+        let is_usb2 = self
+            .usb_type()
+            .is_some_and(|usb_type| usb_type.starts_with('2'));
+
+        is_usb2 && config.requested_bandwidth() > USB2_BANDWIDTH
+    }
+
+    /// Predicate for checking if the device is extendable to the given `extension`.
+    ///
+    /// Returns true iff the device supports the functionality described by `extension`, e.g.
+    /// [`Rs2Extension::AdvancedMode`].
+    ///
+    pub fn is_extendable_to(&self, extension: Rs2Extension) -> bool {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let is_extendable = sys::rs2_is_device_extendable_to(
+                self.device_ptr.as_ptr(),
+                #[allow(clippy::useless_conversion)]
+                (extension as i32).try_into().unwrap(),
+                &mut err,
+            );
+
+            if err.as_ref().is_none() {
+                is_extendable != 0
+            } else {
+                sys::rs2_free_error(err);
+                false
+            }
+        }
+    }
+
+    /// Load a JSON preset onto the device.
+    ///
+    /// JSON presets hold the advanced-mode depth settings of D400 series devices, such as the
+    /// presets recommended by Intel or those exported from the RealSense Viewer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PresetJsonError::AdvancedModeNotSupported`] if the device is not extendable to
+    /// [`Rs2Extension::AdvancedMode`].
+    ///
+    /// Returns [`PresetJsonError::JsonTooLarge`] if the length of `json` does not fit into the size
+    /// type used by librealsense2.
+    ///
+    /// Returns [`PresetJsonError::CouldNotLoadJson`] if the preset could not be loaded, e.g. if it
+    /// is malformed or advanced mode is not enabled.
+    ///
+    pub fn load_preset_json(&self, json: &str) -> Result<(), PresetJsonError> {
+        if !self.is_extendable_to(Rs2Extension::AdvancedMode) {
+            return Err(PresetJsonError::AdvancedModeNotSupported);
+        }
+
+        let content_size = json
+            .len()
+            .try_into()
+            .map_err(|_| PresetJsonError::JsonTooLarge)?;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_load_json(
+                self.device_ptr.as_ptr(),
+                json.as_ptr().cast::<std::os::raw::c_void>(),
+                content_size,
+                &mut err,
+            );
+            check_rs2_error!(err, PresetJsonError::CouldNotLoadJson)
+        }
+    }
+
+    /// Serialize the current settings of the device into a JSON preset.
+    ///
+    /// The returned preset can be loaded again with [`Device::load_preset_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PresetJsonError::AdvancedModeNotSupported`] if the device is not extendable to
+    /// [`Rs2Extension::AdvancedMode`].
+    ///
+    /// Returns [`PresetJsonError::CouldNotSerializeJson`] if the settings could not be serialized.
+    ///
+    pub fn serialize_preset_json(&self) -> Result<String, PresetJsonError> {
+        if !self.is_extendable_to(Rs2Extension::AdvancedMode) {
+            return Err(PresetJsonError::AdvancedModeNotSupported);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let buffer = sys::rs2_serialize_json(self.device_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, PresetJsonError::CouldNotSerializeJson)?;
+
+            let json = read_raw_data(buffer, PresetJsonError::CouldNotSerializeJson)?;
+            Ok(String::from_utf8_lossy(&json).into_owned())
+        }
+    }
+
+    /// Predicate for checking if advanced mode is enabled on the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::AdvancedModeNotSupported`] if the device is not extendable to
+    /// [`Rs2Extension::AdvancedMode`].
+    ///
+    /// Returns [`AdvancedModeError::CouldNotGetAdvancedMode`] if the advanced mode state could not
+    /// be read from the device.
+    ///
+    pub fn is_advanced_mode_enabled(&self) -> Result<bool, AdvancedModeError> {
+        if !self.is_extendable_to(Rs2Extension::AdvancedMode) {
+            return Err(AdvancedModeError::AdvancedModeNotSupported);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut enabled: c_int = 0;
+            sys::rs2_is_enabled(self.device_ptr.as_ptr(), &mut enabled, &mut err);
+            check_rs2_error!(err, AdvancedModeError::CouldNotGetAdvancedMode)?;
+
+            Ok(enabled != 0)
+        }
+    }
+
+    /// Takes ownership of the device and enables or disables advanced mode on it.
+    ///
+    /// Toggling advanced mode causes the device to perform a hardware reset, after which it
+    /// re-enumerates. Ownership of the device is taken as the underlying state can no longer be
+    /// safely retained afterwards (see [`Device::hardware_reset`]). Query the context for the
+    /// device again once it has reconnected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AdvancedModeError::AdvancedModeNotSupported`] if the device is not extendable to
+    /// [`Rs2Extension::AdvancedMode`].
+    ///
+    /// Returns [`AdvancedModeError::CouldNotSetAdvancedMode`] if advanced mode could not be
+    /// toggled.
+    ///
+    pub fn set_advanced_mode(self, enabled: bool) -> Result<(), AdvancedModeError> {
+        if !self.is_extendable_to(Rs2Extension::AdvancedMode) {
+            return Err(AdvancedModeError::AdvancedModeNotSupported);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_toggle_advanced_mode(self.device_ptr.as_ptr(), enabled as c_int, &mut err);
+            check_rs2_error!(err, AdvancedModeError::CouldNotSetAdvancedMode)
+        }
+    }
+
+    /// Read the current calibration table from the device.
+    ///
+    /// The table is returned as the raw bytes used by librealsense2, and can be written back with
+    /// [`Device::set_calibration_table`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationTableError::AutoCalibrationNotSupported`] if the device is not
+    /// extendable to [`Rs2Extension::AutoCalibratedDevice`].
+    ///
+    /// Returns [`CalibrationTableError::CouldNotGetCalibrationTable`] if the table could not be
+    /// read.
+    ///
+    pub fn calibration_table(&self) -> Result<Vec<u8>, CalibrationTableError> {
+        if !self.is_extendable_to(Rs2Extension::AutoCalibratedDevice) {
+            return Err(CalibrationTableError::AutoCalibrationNotSupported);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let buffer = sys::rs2_get_calibration_table(self.device_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, CalibrationTableError::CouldNotGetCalibrationTable)?;
+
+            read_raw_data(buffer, CalibrationTableError::CouldNotGetCalibrationTable)
+        }
+    }
+
+    /// Write a calibration table to the device.
+    ///
+    /// The table is only applied to the dynamic area of the device, i.e. it is lost once the
+    /// device is reset, unless it is also written to flash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationTableError::AutoCalibrationNotSupported`] if the device is not
+    /// extendable to [`Rs2Extension::AutoCalibratedDevice`].
+    ///
+    /// Returns [`CalibrationTableError::TableTooLarge`] if the length of `table` does not fit into
+    /// the size type used by librealsense2.
+    ///
+    /// Returns [`CalibrationTableError::CouldNotSetCalibrationTable`] if the table could not be
+    /// written, e.g. if it is malformed.
+    ///
+    pub fn set_calibration_table(&self, table: &[u8]) -> Result<(), CalibrationTableError> {
+        if !self.is_extendable_to(Rs2Extension::AutoCalibratedDevice) {
+            return Err(CalibrationTableError::AutoCalibrationNotSupported);
+        }
+
+        let table_size = table
+            .len()
+            .try_into()
+            .map_err(|_| CalibrationTableError::TableTooLarge)?;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_set_calibration_table(
+                self.device_ptr.as_ptr(),
+                table.as_ptr().cast::<std::os::raw::c_void>(),
+                table_size,
+                &mut err,
+            );
+            check_rs2_error!(err, CalibrationTableError::CouldNotSetCalibrationTable)
+        }
+    }
+
+    /// Run on-chip self-calibration on the device.
+    ///
+    /// This is the field recalibration procedure for D400 series devices. The device must be
+    /// streaming depth while the calibration runs. The calibration is configured by `json_config`
+    /// (see the librealsense2 documentation of `rs2_run_on_chip_calibration` for the accepted
+    /// keys); if it is empty, the default parameters are used. This call blocks until the
+    /// calibration completes, calling `progress` with the progress exactly as librealsense2
+    /// reports it.
+    ///
+    /// Returns the new calibration table alongside the health score of the calibration. The new
+    /// table is not applied automatically; write it with [`Device::set_calibration_table`] if the
+    /// health score is acceptable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationTableError::AutoCalibrationNotSupported`] if the device is not
+    /// extendable to [`Rs2Extension::AutoCalibratedDevice`].
+    ///
+    /// Returns [`CalibrationTableError::ConfigTooLarge`] if the length of `json_config` does not
+    /// fit into the size type used by librealsense2.
+    ///
+    /// Returns [`CalibrationTableError::CouldNotRunOnChipCalibration`] if the calibration fails.
+    ///
+    pub fn run_on_chip_calibration(
+        &self,
+        json_config: &str,
+        mut progress: impl FnMut(f32),
+    ) -> Result<(Vec<u8>, f32), CalibrationTableError> {
+        if !self.is_extendable_to(Rs2Extension::AutoCalibratedDevice) {
+            return Err(CalibrationTableError::AutoCalibrationNotSupported);
+        }
+
+        let content_size = json_config
+            .len()
+            .try_into()
+            .map_err(|_| CalibrationTableError::ConfigTooLarge)?;
+        let json_ptr = if json_config.is_empty() {
+            std::ptr::null()
+        } else {
+            json_config.as_ptr().cast::<std::os::raw::c_void>()
+        };
+
+        // The progress callback is only invoked on the calling thread while librealsense2 runs
+        // the calibration, so a reference to it can be handed over directly.
+        let mut progress: &mut dyn FnMut(f32) = &mut progress;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut health = 0.0f32;
+            let buffer = sys::rs2_run_on_chip_calibration(
+                self.device_ptr.as_ptr(),
+                json_ptr,
+                content_size,
+                &mut health,
+                Some(progress_trampoline),
+                (&mut progress as *mut &mut dyn FnMut(f32)).cast::<std::os::raw::c_void>(),
+                ON_CHIP_CALIBRATION_TIMEOUT_MS,
+                &mut err,
+            );
+            check_rs2_error!(err, CalibrationTableError::CouldNotRunOnChipCalibration)?;
+
+            let table = read_raw_data(buffer, CalibrationTableError::CouldNotRunOnChipCalibration)?;
+            Ok((table, health))
+        }
+    }
+
+    /// Update the firmware of a device in recovery mode with a signed firmware image.
+    ///
+    /// This is the regular way of updating the firmware: the device is first switched to its
+    /// update state via [`Device::enter_update_state`], after which it reappears in recovery mode
+    /// (see [`Device::is_in_recovery_mode`]) and the image is written to it. The update runs on
+    /// the calling thread, and reports its progress through `progress`, exactly as librealsense2
+    /// reports it. The device reboots into the new firmware once the update completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FirmwareUpdateError::UpdateNotSupported`] if the device is not in recovery mode.
+    ///
+    /// Returns [`FirmwareUpdateError::ImageTooLarge`] if the image is too large to be passed to
+    /// librealsense2.
+    ///
+    /// Returns [`FirmwareUpdateError::CouldNotUpdateFirmware`] if the update fails.
+    ///
+    pub fn update_firmware(
+        &self,
+        image: &[u8],
+        mut progress: impl FnMut(f32),
+    ) -> Result<(), FirmwareUpdateError> {
+        if !self.is_extendable_to(Rs2Extension::UpdateDevice) {
+            return Err(FirmwareUpdateError::UpdateNotSupported);
+        }
+
+        let image_size = image
+            .len()
+            .try_into()
+            .map_err(|_| FirmwareUpdateError::ImageTooLarge)?;
+
+        // As in `run_on_chip_calibration`, the callback is only invoked on the calling thread.
+        let mut progress: &mut dyn FnMut(f32) = &mut progress;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_update_firmware(
+                self.device_ptr.as_ptr(),
+                image.as_ptr().cast::<std::os::raw::c_void>(),
+                image_size,
+                Some(progress_trampoline),
+                (&mut progress as *mut &mut dyn FnMut(f32)).cast::<std::os::raw::c_void>(),
+                &mut err,
+            );
+            check_rs2_error!(err, FirmwareUpdateError::CouldNotUpdateFirmware)
+        }
+    }
+
+    /// Update the firmware of a device by writing an unsigned firmware image directly to its
+    /// flash.
+    ///
+    /// Unlike [`Device::update_firmware`], this works on a device that is running normally, but
+    /// only if its flash is unlocked (see [`Rs2CameraInfo::CameraLocked`]). Only the firmware
+    /// regions of the flash are written, so that the calibration of the device is retained. The
+    /// update runs on the calling thread, and reports its progress through `progress`, exactly as
+    /// librealsense2 reports it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FirmwareUpdateError::UpdateNotSupported`] if the device does not support
+    /// firmware updates.
+    ///
+    /// Returns [`FirmwareUpdateError::ImageTooLarge`] if the image is too large to be passed to
+    /// librealsense2.
+    ///
+    /// Returns [`FirmwareUpdateError::CouldNotUpdateFirmware`] if the update fails, e.g. if the
+    /// flash is locked.
+    ///
+    pub fn update_firmware_unsigned(
+        &self,
+        image: &[u8],
+        mut progress: impl FnMut(f32),
+    ) -> Result<(), FirmwareUpdateError> {
+        if !self.is_extendable_to(Rs2Extension::Updatable) {
+            return Err(FirmwareUpdateError::UpdateNotSupported);
+        }
+
+        let image_size = image
+            .len()
+            .try_into()
+            .map_err(|_| FirmwareUpdateError::ImageTooLarge)?;
+
+        let mut progress: &mut dyn FnMut(f32) = &mut progress;
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_update_firmware_unsigned(
+                self.device_ptr.as_ptr(),
+                image.as_ptr().cast::<std::os::raw::c_void>(),
+                image_size,
+                Some(progress_trampoline),
+                (&mut progress as *mut &mut dyn FnMut(f32)).cast::<std::os::raw::c_void>(),
+                // The other modes also overwrite the read-only regions of the flash, which can
+                // damage the device.
+                sys::RS2_UNSIGNED_UPDATE_MODE_UPDATE as c_int,
+                &mut err,
+            );
+            check_rs2_error!(err, FirmwareUpdateError::CouldNotUpdateFirmware)
+        }
+    }
+
     /// Set realtimeness of the device.
     pub fn set_real_time(&self, realtime: bool) -> bool {
         unsafe {
@@ -238,3 +993,43 @@ impl Device {
         self.device_ptr
     }
 }
+
+/// Trampoline passed to librealsense2 functions reporting progress.
+///
+/// The progress is passed on unchanged. `user` must point to a live `&mut dyn FnMut(f32)`. Panics
+/// in the callback are caught, since unwinding across the FFI boundary is undefined behaviour.
+unsafe extern "C" fn progress_trampoline(progress: f32, user: *mut std::os::raw::c_void) {
+    let callback = &mut *user.cast::<&mut dyn FnMut(f32)>();
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(progress)));
+}
+
+/// Copy the contents of a raw data buffer returned by librealsense2 into a `Vec`.
+///
+/// The buffer is deleted once its contents have been copied, regardless of whether an error
+/// occurred. Any error is reported through `error`.
+///
+/// # Safety
+///
+/// `buffer` must be a valid raw data buffer that is not used after this call.
+unsafe fn read_raw_data<E>(
+    buffer: *const sys::rs2_raw_data_buffer,
+    error: fn(Rs2Exception, String) -> E,
+) -> Result<Vec<u8>, E> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+    let size = sys::rs2_get_raw_data_size(buffer, &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_delete_raw_data(buffer);
+    }
+    check_rs2_error!(err, error)?;
+
+    let data = sys::rs2_get_raw_data(buffer, &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_delete_raw_data(buffer);
+    }
+    check_rs2_error!(err, error)?;
+
+    let contents = std::slice::from_raw_parts(data, size as usize).to_vec();
+    sys::rs2_delete_raw_data(buffer);
+    Ok(contents)
+}
@@ -1,20 +1,39 @@
 //! Defines the frame type including sensor data.
 
+mod any;
 mod composite;
+mod drop_detector;
+mod exposure;
 mod image;
 mod motion;
 mod pixel;
 mod points;
 mod pose;
 mod prelude;
+mod queue;
+mod raw;
+mod reorder;
+mod timestamp;
 
+pub use self::any::{AnyFrame, AnyFrameError};
 pub use self::image::{
-    ColorFrame, ConfidenceFrame, DepthFrame, DisparityFrame, FisheyeFrame, ImageFrame,
-    InfraredFrame,
+    ColorFrame, ConfidenceFrame, DepthFrame, DisparityFrame, DistanceFrame, FisheyeFrame,
+    ImageFrame, InfraredFrame,
 };
 pub use self::motion::{AccelFrame, GyroFrame, MotionFrame};
 pub use self::points::PointsFrame;
 pub use composite::CompositeFrame;
+pub use drop_detector::FrameDropDetector;
+pub use exposure::AutoExposureMonitor;
 pub use pixel::PixelKind;
 pub use pose::{Confidence, PoseFrame};
-pub use prelude::{FrameCategory, FrameConstructionError, FrameEx};
+#[cfg(feature = "jpeg")]
+pub use prelude::JpegEncodingError;
+pub use prelude::{
+    CropError, DisparityConversionError, FrameCategory, FrameConstructionError, FrameEx,
+    HoleFillingError, UndistortError,
+};
+pub use queue::{FrameQueue, FrameQueueError};
+pub use raw::RawFrame;
+pub use reorder::ReorderBuffer;
+pub use timestamp::TimestampNormalizer;
@@ -0,0 +1,294 @@
+//! Type for representing a frame whose type is only known at runtime.
+//!
+//! Most of the time, frames are retrieved from a [`CompositeFrame`](super::CompositeFrame) by
+//! type via [`frames_of_type`](super::CompositeFrame::frames_of_type). However, when handling
+//! heterogeneous framesets it is not always known up front which frame types are present. The
+//! [`AnyFrame`] type inspects the extensions and stream kind of a raw frame and constructs the
+//! matching frame type.
+
+use super::{
+    image::{
+        ColorFrame, ConfidenceFrame, DepthFrame, DisparityFrame, DistanceFrame, FisheyeFrame,
+        InfraredFrame,
+    },
+    motion::{AccelFrame, GyroFrame},
+    points::PointsFrame,
+    pose::PoseFrame,
+    prelude::{FrameConstructionError, FrameEx},
+};
+use crate::{
+    check_rs2_error,
+    kind::{
+        Rs2Exception, Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain,
+    },
+    sensor::Sensor,
+    stream_profile::StreamProfile,
+};
+use anyhow::Result;
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use realsense_sys as sys;
+use std::{
+    convert::{TryFrom, TryInto},
+    ptr::NonNull,
+};
+use thiserror::Error;
+
+/// Occurs when an [`AnyFrame`] cannot be constructed from a raw frame.
+#[derive(Error, Debug)]
+pub enum AnyFrameError {
+    /// Could not check whether the frame is extendable to a given extension.
+    #[error("Could not check frame extension. Type: {0}; Reason: {1}")]
+    CouldNotCheckExtension(Rs2Exception, String),
+    /// The frame does not correspond to any supported frame type.
+    #[error("Frame does not correspond to any supported frame type.")]
+    UnsupportedFrame,
+}
+
+/// A frame of any of the types supported by this crate.
+///
+/// Video frames with a stream kind that does not correspond to a more specific frame type (e.g.
+/// frames produced by a colorizer) are represented as [`AnyFrame::Color`].
+#[derive(Debug, Clone)]
+pub enum AnyFrame {
+    /// A depth frame.
+    Depth(DepthFrame),
+    /// A depth frame in the [`Rs2Format::Distance`] format.
+    Distance(DistanceFrame),
+    /// A disparity frame.
+    Disparity(DisparityFrame),
+    /// A color frame.
+    Color(ColorFrame),
+    /// An infrared frame.
+    Infrared(InfraredFrame),
+    /// A fisheye frame.
+    Fisheye(FisheyeFrame),
+    /// A confidence frame.
+    Confidence(ConfidenceFrame),
+    /// An accelerometer frame.
+    Accel(AccelFrame),
+    /// A gyroscope frame.
+    Gyro(GyroFrame),
+    /// A pose frame.
+    Pose(PoseFrame),
+    /// A points (point cloud) frame.
+    Points(PointsFrame),
+}
+
+/// Checks if the frame behind `frame_ptr` is extendable to `extension`.
+///
+/// # Errors
+///
+/// Returns [`AnyFrameError::CouldNotCheckExtension`] if librealsense2 could not check the
+/// extension.
+unsafe fn is_extendable_to(
+    frame_ptr: NonNull<sys::rs2_frame>,
+    extension: Rs2Extension,
+) -> Result<bool, AnyFrameError> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let is_extendable_to = sys::rs2_is_frame_extendable_to(
+        frame_ptr.as_ptr(),
+        #[allow(clippy::useless_conversion)]
+        (extension as i32).try_into().unwrap(),
+        &mut err,
+    );
+    check_rs2_error!(err, AnyFrameError::CouldNotCheckExtension)?;
+
+    Ok(is_extendable_to != 0)
+}
+
+/// Gets the stream profile of the frame behind `frame_ptr`.
+///
+/// # Errors
+///
+/// Returns [`FrameConstructionError::CouldNotGetFrameStreamProfile`] if the stream profile of the
+/// frame could not be obtained.
+unsafe fn stream_profile(frame_ptr: NonNull<sys::rs2_frame>) -> Result<StreamProfile> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let profile_ptr = sys::rs2_get_frame_stream_profile(frame_ptr.as_ptr(), &mut err);
+    check_rs2_error!(err, FrameConstructionError::CouldNotGetFrameStreamProfile)?;
+
+    let nonnull_profile_ptr = NonNull::new(profile_ptr as *mut sys::rs2_stream_profile).unwrap();
+    Ok(StreamProfile::try_from(nonnull_profile_ptr)?)
+}
+
+/// Gets the stream kind of the frame behind `frame_ptr`.
+///
+/// See [`stream_profile`] for the errors that may occur.
+unsafe fn stream_kind(frame_ptr: NonNull<sys::rs2_frame>) -> Result<Rs2StreamKind> {
+    Ok(stream_profile(frame_ptr)?.kind())
+}
+
+impl AnyFrame {
+    /// Attempt to construct the frame type matching a raw pointer to `rs2_frame`.
+    ///
+    /// The frame's extensions are checked from most to least specific (e.g. a disparity frame is
+    /// also a depth frame, which is also a video frame), and the stream kind is used to pick
+    /// between the frame types that share an extension. Depth frames in the
+    /// [`Rs2Format::Distance`] format are told apart from other depth frames by their format.
+    ///
+    /// On success, the returned frame owns the pointer. On failure, ownership remains with the
+    /// caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnyFrameError::UnsupportedFrame`] if the frame does not correspond to any of the
+    /// supported frame types (e.g. composite frames).
+    ///
+    /// Returns [`AnyFrameError::CouldNotCheckExtension`] if the extensions of the frame could not
+    /// be checked.
+    ///
+    /// Otherwise, returns any error that occurs while constructing the frame type itself (see
+    /// [`FrameConstructionError`]).
+    pub fn from_raw(frame_ptr: NonNull<sys::rs2_frame>) -> Result<Self> {
+        unsafe {
+            if is_extendable_to(frame_ptr, Rs2Extension::Points)? {
+                return Ok(AnyFrame::Points(PointsFrame::try_from(frame_ptr)?));
+            }
+
+            if is_extendable_to(frame_ptr, Rs2Extension::PoseFrame)? {
+                return Ok(AnyFrame::Pose(PoseFrame::try_from(frame_ptr)?));
+            }
+
+            if is_extendable_to(frame_ptr, Rs2Extension::MotionFrame)? {
+                return match stream_kind(frame_ptr)? {
+                    Rs2StreamKind::Accel => Ok(AnyFrame::Accel(AccelFrame::try_from(frame_ptr)?)),
+                    Rs2StreamKind::Gyro => Ok(AnyFrame::Gyro(GyroFrame::try_from(frame_ptr)?)),
+                    _ => Err(AnyFrameError::UnsupportedFrame.into()),
+                };
+            }
+
+            if is_extendable_to(frame_ptr, Rs2Extension::DisparityFrame)? {
+                return Ok(AnyFrame::Disparity(DisparityFrame::try_from(frame_ptr)?));
+            }
+
+            if is_extendable_to(frame_ptr, Rs2Extension::DepthFrame)? {
+                if stream_profile(frame_ptr)?.format() == Rs2Format::Distance {
+                    return Ok(AnyFrame::Distance(DistanceFrame::try_from(frame_ptr)?));
+                }
+                return Ok(AnyFrame::Depth(DepthFrame::try_from(frame_ptr)?));
+            }
+
+            if is_extendable_to(frame_ptr, Rs2Extension::VideoFrame)? {
+                return match stream_kind(frame_ptr)? {
+                    Rs2StreamKind::Infrared => {
+                        Ok(AnyFrame::Infrared(InfraredFrame::try_from(frame_ptr)?))
+                    }
+                    Rs2StreamKind::Fisheye => {
+                        Ok(AnyFrame::Fisheye(FisheyeFrame::try_from(frame_ptr)?))
+                    }
+                    Rs2StreamKind::Confidence => {
+                        Ok(AnyFrame::Confidence(ConfidenceFrame::try_from(frame_ptr)?))
+                    }
+                    _ => Ok(AnyFrame::Color(ColorFrame::try_from(frame_ptr)?)),
+                };
+            }
+
+            Err(AnyFrameError::UnsupportedFrame.into())
+        }
+    }
+
+    /// Get a reference to the contained frame as a [`FrameEx`] trait object.
+    fn as_frame_ex(&self) -> &dyn FrameEx {
+        match self {
+            AnyFrame::Depth(f) => f,
+            AnyFrame::Distance(f) => f,
+            AnyFrame::Disparity(f) => f,
+            AnyFrame::Color(f) => f,
+            AnyFrame::Infrared(f) => f,
+            AnyFrame::Fisheye(f) => f,
+            AnyFrame::Confidence(f) => f,
+            AnyFrame::Accel(f) => f,
+            AnyFrame::Gyro(f) => f,
+            AnyFrame::Pose(f) => f,
+            AnyFrame::Points(f) => f,
+        }
+    }
+
+    /// Move the contained frame into a boxed [`FrameEx`] trait object.
+    pub(crate) fn into_boxed(self) -> Box<dyn FrameEx> {
+        match self {
+            AnyFrame::Depth(f) => Box::new(f),
+            AnyFrame::Distance(f) => Box::new(f),
+            AnyFrame::Disparity(f) => Box::new(f),
+            AnyFrame::Color(f) => Box::new(f),
+            AnyFrame::Infrared(f) => Box::new(f),
+            AnyFrame::Fisheye(f) => Box::new(f),
+            AnyFrame::Confidence(f) => Box::new(f),
+            AnyFrame::Accel(f) => Box::new(f),
+            AnyFrame::Gyro(f) => Box::new(f),
+            AnyFrame::Pose(f) => Box::new(f),
+            AnyFrame::Points(f) => Box::new(f),
+        }
+    }
+}
+
+impl TryFrom<NonNull<sys::rs2_frame>> for AnyFrame {
+    type Error = anyhow::Error;
+
+    /// Attempt to construct the frame type matching a raw pointer to `rs2_frame`.
+    ///
+    /// See [`AnyFrame::from_raw`].
+    fn try_from(frame_ptr: NonNull<sys::rs2_frame>) -> Result<Self, Self::Error> {
+        Self::from_raw(frame_ptr)
+    }
+}
+
+impl FrameEx for AnyFrame {
+    fn stream_profile(&self) -> &StreamProfile {
+        self.as_frame_ex().stream_profile()
+    }
+
+    fn sensor(&self) -> Result<Sensor> {
+        self.as_frame_ex().sensor()
+    }
+
+    fn frame_number(&self) -> u64 {
+        self.as_frame_ex().frame_number()
+    }
+
+    fn timestamp(&self) -> f64 {
+        self.as_frame_ex().timestamp()
+    }
+
+    fn timestamp_domain(&self) -> Rs2TimestampDomain {
+        self.as_frame_ex().timestamp_domain()
+    }
+
+    fn metadata(&self, metadata_kind: Rs2FrameMetadata) -> Option<std::os::raw::c_longlong> {
+        self.as_frame_ex().metadata(metadata_kind)
+    }
+
+    fn supports_metadata(&self, metadata_kind: Rs2FrameMetadata) -> bool {
+        self.as_frame_ex().supports_metadata(metadata_kind)
+    }
+
+    fn keep(&self) {
+        self.as_frame_ex().keep()
+    }
+
+    fn data(&self) -> &[u8] {
+        self.as_frame_ex().data()
+    }
+
+    fn content_hash(&self) -> u64 {
+        self.as_frame_ex().content_hash()
+    }
+
+    unsafe fn get_owned_raw(self) -> NonNull<sys::rs2_frame> {
+        match self {
+            AnyFrame::Depth(f) => f.get_owned_raw(),
+            AnyFrame::Distance(f) => f.get_owned_raw(),
+            AnyFrame::Disparity(f) => f.get_owned_raw(),
+            AnyFrame::Color(f) => f.get_owned_raw(),
+            AnyFrame::Infrared(f) => f.get_owned_raw(),
+            AnyFrame::Fisheye(f) => f.get_owned_raw(),
+            AnyFrame::Confidence(f) => f.get_owned_raw(),
+            AnyFrame::Accel(f) => f.get_owned_raw(),
+            AnyFrame::Gyro(f) => f.get_owned_raw(),
+            AnyFrame::Pose(f) => f.get_owned_raw(),
+            AnyFrame::Points(f) => f.get_owned_raw(),
+        }
+    }
+}
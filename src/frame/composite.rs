@@ -6,8 +6,15 @@
 //!
 //! This is typically what is delivered from the pipeline.
 
-use super::prelude::FrameCategory;
-use crate::kind::Rs2StreamKind;
+use super::{
+    any::AnyFrame,
+    image::{ColorFrame, DepthFrame},
+    prelude::{
+        add_frame_ref, is_frame_extendable_to, FrameCategory, FrameConstructionError, FrameEx,
+    },
+    raw::RawFrame,
+};
+use crate::kind::{Rs2Extension, Rs2FrameMetadata};
 use realsense_sys as sys;
 use std::{
     convert::{TryFrom, TryInto},
@@ -30,9 +37,42 @@ impl Drop for CompositeFrame {
     }
 }
 
-impl From<NonNull<sys::rs2_frame>> for CompositeFrame {
-    fn from(frame_ptr: NonNull<sys::rs2_frame>) -> Self {
-        Self { ptr: frame_ptr }
+/// Cloning a composite frame adds a reference to the underlying librealsense2 frame rather than
+/// copying the frames within it. Each clone releases its own reference when dropped.
+impl Clone for CompositeFrame {
+    fn clone(&self) -> Self {
+        unsafe {
+            add_frame_ref(self.ptr);
+        }
+
+        Self { ptr: self.ptr }
+    }
+}
+
+impl TryFrom<NonNull<sys::rs2_frame>> for CompositeFrame {
+    type Error = FrameConstructionError;
+
+    /// Attempt to construct a composite frame from a raw pointer to an `rs2_frame`.
+    ///
+    /// On success, the composite frame takes ownership of the frame reference. On failure, the
+    /// reference remains owned by the caller.
+    ///
+    /// This replaces the infallible `From<NonNull<rs2_frame>>` conversion of earlier versions,
+    /// which accepted any frame. The two cannot coexist, since `From` implies a `TryFrom` that
+    /// never fails. Code that already knows the pointer refers to a frameset (e.g. one delivered
+    /// by a pipeline) can construct the frame directly as `CompositeFrame { ptr }`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameConstructionError::NotExtendableTo`] if the frame is not a frameset.
+    fn try_from(frame_ptr: NonNull<sys::rs2_frame>) -> Result<Self, Self::Error> {
+        if unsafe { is_frame_extendable_to(frame_ptr, Rs2Extension::CompositeFrame) } {
+            Ok(Self { ptr: frame_ptr })
+        } else {
+            Err(FrameConstructionError::NotExtendableTo(
+                Rs2Extension::CompositeFrame,
+            ))
+        }
     }
 }
 
@@ -56,6 +96,81 @@ impl CompositeFrame {
         self.count() == 0
     }
 
+    /// Keep the composite frame (and every frame within it) alive beyond the callback it was
+    /// delivered to.
+    ///
+    /// See [`FrameEx::keep`](super::FrameEx::keep) for the memory implications of keeping frames.
+    pub fn keep(&self) {
+        unsafe {
+            sys::rs2_keep_frame(self.ptr.as_ptr());
+        }
+    }
+
+    /// Gets the frame number of the composite frame.
+    ///
+    /// This is the frame number of the first frame in the collection. Returns `None` if the
+    /// collection is empty or the frame number could not be obtained.
+    pub fn frame_number(&self) -> Option<u64> {
+        self.with_first_frame(|frame_ptr, err| unsafe { sys::rs2_get_frame_number(frame_ptr, err) })
+    }
+
+    /// Gets the timestamp of the composite frame.
+    ///
+    /// This is the timestamp of the first frame in the collection. Returns `None` if the
+    /// collection is empty or the timestamp could not be obtained.
+    pub fn timestamp(&self) -> Option<f64> {
+        self.with_first_frame(|frame_ptr, err| unsafe {
+            sys::rs2_get_frame_timestamp(frame_ptr, err)
+        })
+    }
+
+    /// Gets the metadata of the composite frame.
+    ///
+    /// This is the metadata of the first frame in the collection. Returns `None` if the collection
+    /// is empty or the metadata is not supported by the frame.
+    pub fn metadata(&self, metadata_kind: Rs2FrameMetadata) -> Option<std::os::raw::c_longlong> {
+        self.with_first_frame(|frame_ptr, err| unsafe {
+            sys::rs2_get_frame_metadata(
+                frame_ptr,
+                #[allow(clippy::useless_conversion)]
+                (metadata_kind as i32).try_into().unwrap(),
+                err,
+            )
+        })
+    }
+
+    /// Extracts the first frame in the collection and applies `f` to it.
+    ///
+    /// Returns `None` if the frame could not be extracted or if `f` reports an error.
+    fn with_first_frame<T>(
+        &self,
+        f: impl FnOnce(*const sys::rs2_frame, *mut *mut sys::rs2_error) -> T,
+    ) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let frame_ptr = sys::rs2_extract_frame(self.ptr.as_ptr(), 0, &mut err);
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return None;
+            }
+
+            let val = f(frame_ptr, &mut err);
+            sys::rs2_release_frame(frame_ptr);
+
+            if err.as_ref().is_none() {
+                Some(val)
+            } else {
+                sys::rs2_free_error(err);
+                None
+            }
+        }
+    }
+
     /// Retrieves all frames in the Composite frame collection of a given type.
     ///
     /// # Generic Arguments
@@ -73,47 +188,84 @@ impl CompositeFrame {
     where
         F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
     {
-        let mut frames = Vec::new();
-        for i in 0..self.count() {
-            unsafe {
-                let mut err = std::ptr::null_mut::<sys::rs2_error>();
-                let frame_ptr =
-                    sys::rs2_extract_frame(self.ptr.as_ptr(), i as std::os::raw::c_int, &mut err);
-
-                if err.as_ref().is_some() {
-                    sys::rs2_free_error(err);
-                    continue;
-                }
-
-                let nonnull_frame_ptr = NonNull::new(frame_ptr).unwrap();
-
-                let is_extendable_to = sys::rs2_is_frame_extendable_to(
-                    nonnull_frame_ptr.as_ptr(),
-                    #[allow(clippy::useless_conversion)]
-                    (F::extension() as i32).try_into().unwrap(),
-                    &mut err,
-                );
-
-                if err.as_ref().is_none() {
-                    if is_extendable_to != 0 {
-                        if let Ok(f) = F::try_from(nonnull_frame_ptr) {
-                            let kind_for_frame = F::kind();
-
-                            if kind_for_frame == Rs2StreamKind::Any || f.has_correct_kind() {
-                                frames.push(f);
-                            }
-                            // This continue is to skip releasing the frame at the end of the loop.
-                            // If the call to try_from above is successful and we can push, then
-                            // the frame is owned by the type `E` and we should not release it.
-                            continue;
-                        }
-                    }
-                } else {
-                    sys::rs2_free_error(err);
-                }
-                sys::rs2_release_frame(nonnull_frame_ptr.as_ptr());
+        (0..self.count())
+            .filter_map(|i| self.frame_of_type(i))
+            .collect()
+    }
+
+    /// Retrieves the first frame in the Composite frame collection of a given type.
+    ///
+    /// The returned frame owns its own reference to the underlying frame, so it remains valid
+    /// after the collection is dropped. Returns `None` if the collection holds no frame of that
+    /// type.
+    ///
+    /// See [`CompositeFrame::frames_of_type`] for the types that can be used for `F`.
+    ///
+    pub fn first_of_kind<F>(&self) -> Option<F>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        (0..self.count()).find_map(|i| self.frame_of_type(i))
+    }
+
+    /// Retrieves the first depth frame in the Composite frame collection, if any.
+    pub fn depth_frame(&self) -> Option<DepthFrame> {
+        self.first_of_kind()
+    }
+
+    /// Retrieves the first color frame in the Composite frame collection, if any.
+    pub fn color_frame(&self) -> Option<ColorFrame> {
+        self.first_of_kind()
+    }
+
+    /// Retrieves all frames in the Composite frame collection, regardless of their type.
+    ///
+    /// Each frame is constructed as its concrete frame type (see [`AnyFrame`]) and boxed behind
+    /// the [`FrameEx`] trait, e.g. for logging or recording code that treats all frames alike.
+    /// Frames that do not correspond to any supported frame type are skipped.
+    ///
+    pub fn frames(&self) -> Vec<Box<dyn FrameEx>> {
+        (0..self.count())
+            .filter_map(|i| self.get(i))
+            .map(AnyFrame::into_boxed)
+            .collect()
+    }
+
+    /// Gets the frame at `index` in the collection, constructed as its concrete frame type.
+    ///
+    /// The returned frame owns its own reference to the underlying frame, so it remains valid
+    /// after the collection is dropped. Returns `None` if `index` is out of bounds, or if the
+    /// frame does not correspond to any supported frame type.
+    ///
+    pub fn get(&self, index: usize) -> Option<AnyFrame> {
+        self.extract(index)?.into_frame().ok()
+    }
+
+    /// Extracts the frame at `index` in the collection, if it is of type `F`.
+    ///
+    /// The extracted frame is released again unless it is returned.
+    fn frame_of_type<F>(&self, index: usize) -> Option<F>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        self.extract(index)?.try_extract()
+    }
+
+    /// Extracts a new reference to the frame at `index` in the collection.
+    ///
+    /// Returns `None` if the frame could not be extracted, e.g. because `index` is out of bounds.
+    fn extract(&self, index: usize) -> Option<RawFrame> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let frame_ptr =
+                sys::rs2_extract_frame(self.ptr.as_ptr(), index as std::os::raw::c_int, &mut err);
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return None;
             }
+
+            Some(RawFrame::from_queued_raw(NonNull::new(frame_ptr)?))
         }
-        frames
     }
 }
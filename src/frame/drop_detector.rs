@@ -0,0 +1,88 @@
+//! Type for detecting dropped frames across consecutive framesets.
+//!
+//! librealsense2 assigns every frame of a stream a monotonically increasing frame number. If a
+//! frame is dropped anywhere between the device and the application, the frame numbers of two
+//! consecutive frames of that stream will differ by more than one. The [`FrameDropDetector`]
+//! keeps track of the last frame number seen for every stream kind and reports these gaps.
+
+use super::prelude::FrameEx;
+use crate::kind::Rs2StreamKind;
+use std::collections::HashMap;
+
+/// Tracks the last seen frame number per stream kind and reports gaps between frames.
+///
+/// Frame numbers that are not larger than the last seen frame number (e.g. after a device or
+/// pipeline restart) are not reported as drops; instead, tracking restarts from that frame.
+#[derive(Debug, Default, Clone)]
+pub struct FrameDropDetector {
+    /// The last frame number observed for each stream kind.
+    last_frame_numbers: HashMap<Rs2StreamKind, u64>,
+}
+
+impl FrameDropDetector {
+    /// Construct a new detector that has not observed any frames yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Observe a frame number for the given stream kind.
+    ///
+    /// Returns the number of frames dropped between the previously observed frame of this stream
+    /// kind and this one, or `None` if no frames were dropped (or this is the first frame observed
+    /// for the stream kind).
+    pub fn observe(&mut self, kind: Rs2StreamKind, frame_number: u64) -> Option<u64> {
+        let last = self.last_frame_numbers.insert(kind, frame_number)?;
+
+        if frame_number > last + 1 {
+            Some(frame_number - last - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Observe a frame, using the stream kind of its stream profile.
+    ///
+    /// See [`FrameDropDetector::observe`] for the returned value.
+    pub fn observe_frame<F: FrameEx>(&mut self, frame: &F) -> Option<u64> {
+        self.observe(frame.stream_profile().kind(), frame.frame_number())
+    }
+
+    /// Get the last frame number observed for the given stream kind, if any.
+    pub fn last_frame_number(&self, kind: Rs2StreamKind) -> Option<u64> {
+        self.last_frame_numbers.get(&kind).copied()
+    }
+
+    /// Forget all previously observed frame numbers.
+    pub fn reset(&mut self) {
+        self.last_frame_numbers.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_gaps_per_stream_kind() {
+        let mut detector = FrameDropDetector::new();
+
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 10), None);
+        assert_eq!(detector.observe(Rs2StreamKind::Color, 3), None);
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 11), None);
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 14), Some(2));
+        assert_eq!(detector.observe(Rs2StreamKind::Color, 4), None);
+        assert_eq!(detector.last_frame_number(Rs2StreamKind::Depth), Some(14));
+    }
+
+    #[test]
+    fn restarted_stream_is_not_a_drop() {
+        let mut detector = FrameDropDetector::new();
+
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 100), None);
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 1), None);
+        assert_eq!(detector.observe(Rs2StreamKind::Depth, 2), None);
+
+        detector.reset();
+        assert_eq!(detector.last_frame_number(Rs2StreamKind::Depth), None);
+    }
+}
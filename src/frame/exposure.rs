@@ -0,0 +1,107 @@
+//! Type for detecting when auto-exposure has converged.
+//!
+//! With auto-exposure enabled, a sensor adjusts its exposure over the first frames of a stream (or
+//! after the lighting changes), so those frames may be over- or underexposed. The
+//! [`AutoExposureMonitor`] follows the
+//! [`ActualExposure`](crate::kind::Rs2FrameMetadata::ActualExposure) metadata of consecutive
+//! frames and reports once it has settled.
+
+use super::prelude::FrameEx;
+
+/// Tracks the exposure of consecutive frames and reports when it has stabilized.
+///
+/// The exposure is considered stable once a number of consecutive frames have an exposure within
+/// a relative tolerance of the first frame of that run. Any frame outside of the tolerance starts a
+/// new run, so the monitor also detects when auto-exposure starts adjusting again.
+#[derive(Debug, Clone)]
+pub struct AutoExposureMonitor {
+    /// The allowed deviation from the reference exposure, as a fraction of it.
+    tolerance: f64,
+    /// The number of consecutive frames within the tolerance required for the exposure to be
+    /// stable.
+    required_frames: usize,
+    /// The exposure of the first frame of the current run, in microseconds.
+    reference_usec: Option<i64>,
+    /// The number of consecutive frames within the tolerance of the reference exposure.
+    run_length: usize,
+}
+
+impl AutoExposureMonitor {
+    /// Construct a new monitor which considers the exposure stable once `required_frames`
+    /// consecutive frames deviate by at most `tolerance` (a fraction, e.g. `0.05` for 5%) from the
+    /// first of them.
+    pub fn new(tolerance: f64, required_frames: usize) -> Self {
+        Self {
+            tolerance,
+            required_frames,
+            reference_usec: None,
+            run_length: 0,
+        }
+    }
+
+    /// Observe the exposure of a frame in microseconds.
+    ///
+    /// Returns whether the exposure is stable after observing it, see
+    /// [`AutoExposureMonitor::is_stable`].
+    pub fn observe(&mut self, exposure_usec: i64) -> bool {
+        match self.reference_usec {
+            Some(reference_usec)
+                if (exposure_usec - reference_usec).abs() as f64
+                    <= self.tolerance * reference_usec as f64 =>
+            {
+                self.run_length += 1;
+            }
+            _ => {
+                self.reference_usec = Some(exposure_usec);
+                self.run_length = 1;
+            }
+        }
+
+        self.is_stable()
+    }
+
+    /// Observe a frame, using its [exposure metadata](FrameEx::exposure_usec).
+    ///
+    /// Frames which do not support the exposure metadata are ignored. See
+    /// [`AutoExposureMonitor::observe`] for the returned value.
+    pub fn observe_frame<F: FrameEx>(&mut self, frame: &F) -> bool {
+        match frame.exposure_usec() {
+            Some(exposure_usec) => self.observe(exposure_usec),
+            None => self.is_stable(),
+        }
+    }
+
+    /// Predicate for whether the exposure of the last observed frames has stabilized.
+    pub fn is_stable(&self) -> bool {
+        self.reference_usec.is_some() && self.run_length >= self.required_frames
+    }
+
+    /// Forget all previously observed exposures, e.g. after changing exposure settings.
+    pub fn reset(&mut self) {
+        self.reference_usec = None;
+        self.run_length = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposure_is_stable_after_consecutive_frames_within_tolerance() {
+        let mut monitor = AutoExposureMonitor::new(0.05, 3);
+
+        assert!(!monitor.observe(1000));
+        assert!(!monitor.observe(5000));
+        assert!(!monitor.observe(8000));
+        assert!(!monitor.observe(8200));
+        assert!(monitor.observe(7900));
+        assert!(monitor.observe(8100));
+
+        // A change in lighting makes auto-exposure adjust again.
+        assert!(!monitor.observe(4000));
+
+        monitor.reset();
+        assert!(!monitor.is_stable());
+    }
+}
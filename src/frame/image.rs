@@ -4,19 +4,29 @@
 //!
 //! - Depth Frame: A depth frame taken from a synthetic depth camera.
 //! - Disparity Frame: A disparity frame taken from a synthetic depth camera.
+//! - Distance Frame: A depth frame holding the distance of every pixel in metres.
 //! - Color Frame: A frame holding color or monochrome data.
 //!
 //! Each frame type can hold data in multiple formats. The data type presented
 //! depends on the settings and flags used at runtime on the RealSense device.
 
+use super::any::AnyFrame;
 use super::pixel::{get_pixel, PixelKind};
+#[cfg(feature = "jpeg")]
+use super::prelude::JpegEncodingError;
 use super::prelude::{
-    CouldNotGetFrameSensorError, DepthError, DisparityError, FrameCategory, FrameConstructionError,
-    FrameEx, BITS_PER_BYTE,
+    add_frame_ref, fnv1a, is_frame_extendable_to, CouldNotGetFrameSensorError, CropError,
+    DepthError, DisparityConversionError, DisparityError, FrameCategory, FrameConstructionError,
+    FrameEx, HoleFillingError, UndistortError, BITS_PER_BYTE,
 };
+use super::queue::FrameQueue;
 use crate::{
+    base::{Rs2Intrinsics, Rs2Roi},
     check_rs2_error,
-    kind::{Rs2Extension, Rs2FrameMetadata, Rs2Option, Rs2StreamKind, Rs2TimestampDomain},
+    kind::{
+        Rs2DistortionModel, Rs2Exception, Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2Option,
+        Rs2StreamKind, Rs2TimestampDomain,
+    },
     sensor::Sensor,
     stream_profile::StreamProfile,
 };
@@ -24,20 +34,29 @@ use anyhow::Result;
 #[allow(unused_imports)]
 use num_traits::FromPrimitive;
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use realsense_sys as sys;
 use std::{
     convert::{TryFrom, TryInto},
     marker::PhantomData,
-    os::raw::c_int,
+    ops::Range,
+    os::raw::{c_int, c_void},
     ptr::{self, NonNull},
 };
 
+/// The number of iterations used to invert the inverse Brown-Conrady distortion model.
+const INVERSE_DISTORTION_ITERATIONS: usize = 10;
+
 /// A unit struct defining a Depth frame.
 #[derive(Debug)]
 pub struct Depth;
 /// A unit struct defining a Disparity frame.
 #[derive(Debug)]
 pub struct Disparity;
+/// A unit struct defining a Distance frame.
+#[derive(Debug)]
+pub struct Distance;
 /// A unit struct defining a Color frame.
 #[derive(Debug)]
 pub struct Color;
@@ -131,6 +150,13 @@ pub type DepthFrame = ImageFrame<Depth>;
 /// Everything called from here during runtime should be valid as long as the
 /// Frame is in scope... like normal Rust.
 pub type DisparityFrame = ImageFrame<Disparity>;
+/// An ImageFrame type holding the raw pointer and derived metadata for an RS2 Depth frame in the
+/// [`Rs2Format::Distance`] format.
+///
+/// All fields in this struct are initialized during struct creation (via `try_from`).
+/// Everything called from here during runtime should be valid as long as the
+/// Frame is in scope... like normal Rust.
+pub type DistanceFrame = ImageFrame<Distance>;
 /// An ImageFrame type holding the raw pointer and derived metadata for an RS2 Color frame.
 ///
 /// All fields in this struct are initialized during struct creation (via `try_from`).
@@ -166,6 +192,32 @@ impl<K> Drop for ImageFrame<K> {
     }
 }
 
+/// Cloning a frame adds a reference to the underlying librealsense2 frame rather than copying its
+/// data. Each clone releases its own reference when dropped.
+impl<K> Clone for ImageFrame<K> {
+    fn clone(&self) -> Self {
+        unsafe {
+            add_frame_ref(self.frame_ptr);
+        }
+
+        Self {
+            frame_ptr: self.frame_ptr,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            bits_per_pixel: self.bits_per_pixel,
+            timestamp: self.timestamp,
+            timestamp_domain: self.timestamp_domain,
+            frame_number: self.frame_number,
+            frame_stream_profile: self.frame_stream_profile.share(),
+            data_size_in_bytes: self.data_size_in_bytes,
+            data: self.data,
+            should_drop: true,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<'a, K> IntoIterator for &'a ImageFrame<K> {
     type Item = <Iter<'a, K> as Iterator>::Item;
     type IntoIter = Iter<'a, K>;
@@ -235,7 +287,20 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for ImageFrame<K> {
             let size = sys::rs2_get_frame_data_size(frame_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, FrameConstructionError::CouldNotGetDataSize)?;
 
-            debug_assert_eq!(size, width * height * bits_per_pixel / BITS_PER_BYTE);
+            let expected = required_data_size(width, height, bits_per_pixel, stride).ok_or(
+                FrameConstructionError::InvalidDimensions {
+                    width,
+                    height,
+                    bits_per_pixel,
+                },
+            )?;
+            if size < 0 || (size as usize) < expected {
+                return Err(FrameConstructionError::DataSizeMismatch {
+                    expected,
+                    actual: size.max(0) as usize,
+                }
+                .into());
+            }
 
             let data_ptr = sys::rs2_get_frame_data(frame_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, FrameConstructionError::CouldNotGetData)?;
@@ -261,6 +326,35 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for ImageFrame<K> {
     }
 }
 
+/// Compute the minimum size in bytes of the frame data from the frame dimensions.
+///
+/// The pixel accessors read `height` rows which start `stride` bytes apart, and each hold
+/// `width * bits_per_pixel / 8` bytes (rounded up to whole bytes). The last row may therefore
+/// lack the padding of the other rows, and the data may be larger than this size.
+///
+/// Returns `None` if any dimension is negative or if the computation overflows.
+fn required_data_size(
+    width: c_int,
+    height: c_int,
+    bits_per_pixel: c_int,
+    stride: c_int,
+) -> Option<usize> {
+    let width = usize::try_from(width).ok()?;
+    let height = usize::try_from(height).ok()?;
+    let bits_per_pixel = usize::try_from(bits_per_pixel).ok()?;
+    let stride = usize::try_from(stride).ok()?;
+
+    if height == 0 {
+        return Some(0);
+    }
+
+    let row_size = width
+        .checked_mul(bits_per_pixel)?
+        .checked_add(BITS_PER_BYTE as usize - 1)?
+        / BITS_PER_BYTE as usize;
+    stride.checked_mul(height - 1)?.checked_add(row_size)
+}
+
 impl FrameCategory for DepthFrame {
     fn extension() -> Rs2Extension {
         Rs2Extension::DepthFrame
@@ -271,7 +365,9 @@ impl FrameCategory for DepthFrame {
     }
 
     fn has_correct_kind(&self) -> bool {
+        // Depth frames in the distance format are represented by `DistanceFrame`.
         self.frame_stream_profile.kind() == Self::kind()
+            && self.frame_stream_profile.format() != Rs2Format::Distance
     }
 }
 
@@ -284,8 +380,25 @@ impl FrameCategory for DisparityFrame {
         Rs2StreamKind::Any
     }
 
+    /// Disparity frames can come from any stream, so rather than comparing the stream kind this
+    /// checks that the frame actually is a disparity frame.
+    fn has_correct_kind(&self) -> bool {
+        unsafe { is_frame_extendable_to(self.frame_ptr, Self::extension()) }
+    }
+}
+
+impl FrameCategory for DistanceFrame {
+    fn extension() -> Rs2Extension {
+        Rs2Extension::DepthFrame
+    }
+
+    fn kind() -> Rs2StreamKind {
+        Rs2StreamKind::Depth
+    }
+
     fn has_correct_kind(&self) -> bool {
         self.frame_stream_profile.kind() == Self::kind()
+            && self.frame_stream_profile.format() == Rs2Format::Distance
     }
 }
 
@@ -416,6 +529,20 @@ impl<T> FrameEx for ImageFrame<T> {
         }
     }
 
+    fn keep(&self) {
+        unsafe {
+            sys::rs2_keep_frame(self.frame_ptr.as_ptr());
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        self.data_bytes()
+    }
+
+    fn content_hash(&self) -> u64 {
+        fnv1a(self.rows())
+    }
+
     unsafe fn get_owned_raw(mut self) -> NonNull<sys::rs2_frame> {
         self.should_drop = false;
 
@@ -454,6 +581,220 @@ impl DepthFrame {
         })?;
         Ok(depth_units)
     }
+
+    /// Get the laser power with which this frame was captured.
+    ///
+    /// The power is a relative measure between 0 and 360. Returns `None` if the
+    /// [`FrameLaserPower`](Rs2FrameMetadata::FrameLaserPower) metadata is not supported by the
+    /// frame.
+    pub fn laser_power(&self) -> Option<i64> {
+        self.metadata(Rs2FrameMetadata::FrameLaserPower)
+    }
+
+    /// Get the emitter mode with which this frame was captured, e.g. 0 if all emitters were
+    /// disabled and 1 if the laser was enabled.
+    ///
+    /// Returns `None` if the [`FrameEmitterMode`](Rs2FrameMetadata::FrameEmitterMode) metadata
+    /// is not supported by the frame.
+    pub fn emitter_mode(&self) -> Option<i64> {
+        self.metadata(Rs2FrameMetadata::FrameEmitterMode)
+    }
+
+    /// Given a column and row index, get the raw depth value from this frame.
+    ///
+    /// Returns `None` if the indices are out of bounds, or if the frame is not in the
+    /// [`Rs2Format::Z16`] format. The value is in depth units (see
+    /// [`DepthFrame::depth_units`]).
+    pub fn get_depth(&self, col: usize, row: usize) -> Option<u16> {
+        if self.frame_stream_profile.format() != Rs2Format::Z16 {
+            return None;
+        }
+
+        match self.get(col, row)? {
+            PixelKind::Z16 { depth } => Some(*depth),
+            _ => None,
+        }
+    }
+
+    /// Get the minimum and maximum valid raw depth values in this frame.
+    ///
+    /// Zero-valued pixels are invalid (no depth could be computed) and are ignored. The values are
+    /// in depth units (see [`DepthFrame::depth_units`]).
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Z16`] format or if no pixel in the
+    /// frame is valid.
+    pub fn min_max_units(&self) -> Option<(u16, u16)> {
+        self.z16_rows()?
+            .flatten()
+            .filter(|depth| **depth != 0)
+            .fold(None, |min_max, &depth| match min_max {
+                None => Some((depth, depth)),
+                Some((min, max)) => Some((min.min(depth), max.max(depth))),
+            })
+    }
+
+    /// Get the ratio of valid (non-zero) pixels to all pixels in this frame.
+    ///
+    /// Returns a value between 0 and 1, or 0 if the frame is empty or not in the
+    /// [`Rs2Format::Z16`] format.
+    pub fn valid_pixel_ratio(&self) -> f32 {
+        let total = self.width * self.height;
+        if total == 0 {
+            return 0.0;
+        }
+
+        match self.z16_rows() {
+            Some(rows) => {
+                let valid = rows.flatten().filter(|depth| **depth != 0).count();
+                valid as f32 / total as f32
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Copy the raw depth values of this frame into an owned buffer, in row-major order.
+    ///
+    /// Any padding at the end of each row is skipped, so the buffer holds exactly
+    /// `width * height` values. The values are in depth units (see
+    /// [`DepthFrame::depth_units`]).
+    ///
+    /// Returns an empty buffer if the frame is not in the [`Rs2Format::Z16`] format.
+    pub fn to_vec(&self) -> Vec<u16> {
+        self.to_vec_decimated(1).2
+    }
+
+    /// Copy every `factor`-th raw depth value of every `factor`-th row of this frame into an owned
+    /// buffer, in row-major order.
+    ///
+    /// Returns the `(width, height, values)` of the decimated image. A `factor` of 0 is treated as
+    /// 1, i.e. no decimation. The values are in depth units (see [`DepthFrame::depth_units`]).
+    ///
+    /// Returns an empty image if the frame is not in the [`Rs2Format::Z16`] format.
+    pub fn to_vec_decimated(&self, factor: usize) -> (usize, usize, Vec<u16>) {
+        match self.z16_rows() {
+            Some(rows) => decimate(rows, self.width, self.height, factor),
+            None => (0, 0, Vec::new()),
+        }
+    }
+
+    /// Iterate over the rows of this frame as slices of raw depth values, without copying.
+    ///
+    /// Any padding at the end of each row is skipped, so each row holds exactly `width` values. The
+    /// values are in depth units (see [`DepthFrame::depth_units`]).
+    ///
+    /// No rows are yielded if the frame is not in the [`Rs2Format::Z16`] format.
+    pub fn rows_u16(&self) -> impl Iterator<Item = &[u16]> {
+        self.z16_rows().into_iter().flatten()
+    }
+
+    /// Produce a copy of this frame in which every invalid (zero) pixel is replaced by the nearest
+    /// valid pixel in the same row.
+    ///
+    /// If the nearest valid pixels to the left and to the right are equally far away, the left one
+    /// is used. Rows without any valid pixel are left as-is. The hole filling is done in software,
+    /// and the output keeps the stream profile and metadata of this frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HoleFillingError::UnsupportedFormat`] if the frame is not in the
+    /// [`Rs2Format::Z16`] format.
+    ///
+    /// Returns any other [`HoleFillingError`] if the filled frame could not be produced, and any
+    /// error that occurs while waiting for it (see [`FrameQueue::wait_for_frame`]).
+    pub fn filled_copy(&self) -> Result<DepthFrame> {
+        let format = self.frame_stream_profile.format();
+        let rows = self
+            .z16_rows()
+            .ok_or(HoleFillingError::UnsupportedFormat(format))?;
+
+        let mut data = Vec::with_capacity(self.width * self.height * std::mem::size_of::<u16>());
+        let mut filled = Vec::with_capacity(self.width);
+        for row in rows {
+            filled.clear();
+            filled.extend_from_slice(row);
+            fill_row_holes(&mut filled);
+            data.extend(filled.iter().flat_map(|depth| depth.to_ne_bytes()));
+        }
+
+        let synthetic = SyntheticFrame {
+            data,
+            width: self.width as c_int,
+            height: self.height as c_int,
+            stride: (self.width * std::mem::size_of::<u16>()) as c_int,
+            bytes_per_pixel: std::mem::size_of::<u16>() as c_int,
+            extension: sys::rs2_extension_RS2_EXTENSION_DEPTH_FRAME,
+            could_not_allocate: HoleFillingError::CouldNotAllocateFrame,
+            result: Ok(()),
+        };
+        let frame = unsafe {
+            synthesize_frame(
+                self.frame_ptr,
+                synthetic,
+                HoleFillingError::CouldNotCreateProcessingBlock,
+                HoleFillingError::CouldNotAddFrameReference,
+                HoleFillingError::CouldNotProcessFrame,
+            )?
+        };
+
+        match frame {
+            AnyFrame::Depth(frame) => Ok(frame),
+            _ => Err(HoleFillingError::DidNotProduceDepthFrame.into()),
+        }
+    }
+
+    /// Iterate over the rows of a [`Rs2Format::Z16`] frame as slices of raw depth values.
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Z16`] format.
+    fn z16_rows(&self) -> Option<impl Iterator<Item = &[u16]>> {
+        if self.frame_stream_profile.format() != Rs2Format::Z16 {
+            return None;
+        }
+
+        let stride = self.stride / std::mem::size_of::<u16>();
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                self.data.as_ptr().cast::<u16>(),
+                self.data_size_in_bytes / std::mem::size_of::<u16>(),
+            )
+        };
+
+        Some((0..self.height).map(move |row| &data[row * stride..row * stride + self.width]))
+    }
+}
+
+impl DistanceFrame {
+    /// Get the distance of every pixel in metres, in row-major order.
+    ///
+    /// Returns an empty slice if the frame is not in the [`Rs2Format::Distance`] format.
+    pub fn as_slice_f32(&self) -> &[f32] {
+        if self.frame_stream_profile.format() != Rs2Format::Distance {
+            return &[];
+        }
+
+        // The frame data is allocated by librealsense2 for 32-bit floats, and its size was
+        // validated against the frame dimensions during construction.
+        unsafe {
+            std::slice::from_raw_parts(
+                self.data.as_ptr().cast::<f32>(),
+                self.data_size_in_bytes / std::mem::size_of::<f32>(),
+            )
+        }
+    }
+
+    /// Given a column and row index, get the distance of the pixel in metres.
+    ///
+    /// Returns `None` if the indices are out of bounds, or if the frame is not in the
+    /// [`Rs2Format::Distance`] format.
+    pub fn get_meters(&self, col: usize, row: usize) -> Option<f32> {
+        if self.frame_stream_profile.format() != Rs2Format::Distance {
+            return None;
+        }
+
+        match self.get(col, row)? {
+            PixelKind::Distance { distance } => Some(*distance),
+            _ => None,
+        }
+    }
 }
 
 impl DisparityFrame {
@@ -467,7 +808,7 @@ impl DisparityFrame {
     ///
     /// It is often much more efficient to directly stream the
     /// [`Rs2Format::Distance`](crate::kind::Rs2Format::Distance) format if you want the distance
-    /// directly, and access the frame data with [`ImageFrame::get`].
+    /// directly, and access the frame data through a [`DistanceFrame`].
     pub fn distance(&self, col: usize, row: usize) -> Result<f32, DepthError> {
         unsafe {
             let mut err = ptr::null_mut::<sys::rs2_error>();
@@ -501,6 +842,445 @@ impl DisparityFrame {
             Ok(baseline)
         }
     }
+
+    /// Convert the disparity frame into a depth frame.
+    ///
+    /// This runs the frame through librealsense2's disparity transform processing block, which
+    /// derives depth from the disparity using the baseline and focal length of the stream. The
+    /// disparity frame itself is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DisparityConversionError`] if the processing block could not be created or
+    /// could not process the frame.
+    ///
+    /// Returns any error that occurs while waiting for the processed frame (see
+    /// [`FrameQueue::wait_for_frame`]).
+    pub fn to_depth_frame(&self) -> Result<DepthFrame> {
+        let mut queue = FrameQueue::with_capacity(1)?;
+
+        unsafe {
+            let mut err = ptr::null_mut::<sys::rs2_error>();
+            let block_ptr = sys::rs2_create_disparity_transform_block(0, &mut err);
+            check_rs2_error!(err, DisparityConversionError::CouldNotCreateTransform)?;
+
+            let processed = process_frame(
+                block_ptr,
+                self.frame_ptr,
+                &queue,
+                DisparityConversionError::CouldNotAddFrameReference,
+                DisparityConversionError::CouldNotProcessFrame,
+            );
+            sys::rs2_delete_processing_block(block_ptr);
+            processed?;
+        }
+
+        match queue.wait_for_frame(None)? {
+            AnyFrame::Depth(frame) => Ok(frame),
+            _ => Err(DisparityConversionError::DidNotProduceDepthFrame.into()),
+        }
+    }
+}
+
+/// Pass a new reference to `frame_ptr` through `block_ptr`, sending its output to `queue`.
+///
+/// # Errors
+///
+/// Returns the error built by `could_not_add_ref` if a reference to the frame could not be added,
+/// and the error built by `could_not_process` if the frame could not be processed.
+unsafe fn process_frame<E>(
+    block_ptr: *mut sys::rs2_processing_block,
+    frame_ptr: NonNull<sys::rs2_frame>,
+    queue: &FrameQueue,
+    could_not_add_ref: fn(Rs2Exception, String) -> E,
+    could_not_process: fn(Rs2Exception, String) -> E,
+) -> Result<(), E> {
+    let mut err = ptr::null_mut::<sys::rs2_error>();
+
+    sys::rs2_start_processing_queue(block_ptr, queue.get_raw().as_ptr(), &mut err);
+    check_rs2_error!(err, could_not_process)?;
+
+    // `rs2_process_frame` takes ownership of the frame it is given, so hand it a new reference
+    // rather than our own.
+    sys::rs2_frame_add_ref(frame_ptr.as_ptr(), &mut err);
+    check_rs2_error!(err, could_not_add_ref)?;
+
+    sys::rs2_process_frame(block_ptr, frame_ptr.as_ptr(), &mut err);
+    check_rs2_error!(err, could_not_process)
+}
+
+/// The pixel data of a frame to be produced by [`synthetic_frame_trampoline`].
+struct SyntheticFrame<E> {
+    /// The pixel data, laid out with `stride` bytes per row.
+    data: Vec<u8>,
+    /// The width of the frame in pixels.
+    width: c_int,
+    /// The height of the frame in pixels.
+    height: c_int,
+    /// The row stride of the frame in bytes.
+    stride: c_int,
+    /// The number of bytes per pixel.
+    bytes_per_pixel: c_int,
+    /// The extension of the frame to allocate, e.g. a video or a depth frame.
+    extension: sys::rs2_extension,
+    /// The error raised if the frame could not be allocated or emitted.
+    could_not_allocate: fn(Rs2Exception, String) -> E,
+    /// The outcome of producing the frame, set once the frame has been processed.
+    result: Result<(), E>,
+}
+
+/// Processing block callback which emits a copy of the incoming frame holding new pixel data.
+///
+/// `user` must point to a live [`SyntheticFrame<E>`]. The incoming frame is released, as the
+/// callback owns it.
+unsafe extern "C" fn synthetic_frame_trampoline<E>(
+    frame_ptr: *mut sys::rs2_frame,
+    source: *mut sys::rs2_source,
+    user: *mut c_void,
+) {
+    let synthetic = &mut *user.cast::<SyntheticFrame<E>>();
+    synthetic.result = emit_synthetic_frame(frame_ptr, source, synthetic);
+    sys::rs2_release_frame(frame_ptr);
+}
+
+/// Allocate a frame with the same stream profile and metadata as `frame_ptr`, fill it with the
+/// pixel data in `synthetic` and hand it to `source`.
+///
+/// # Errors
+///
+/// Returns the `could_not_allocate` error of `synthetic` if the frame could not be allocated or
+/// emitted.
+unsafe fn emit_synthetic_frame<E>(
+    frame_ptr: *mut sys::rs2_frame,
+    source: *mut sys::rs2_source,
+    synthetic: &SyntheticFrame<E>,
+) -> Result<(), E> {
+    let mut err = ptr::null_mut::<sys::rs2_error>();
+
+    let profile_ptr = sys::rs2_get_frame_stream_profile(frame_ptr, &mut err);
+    check_rs2_error!(err, synthetic.could_not_allocate)?;
+
+    let new_frame_ptr = sys::rs2_allocate_synthetic_video_frame(
+        source,
+        profile_ptr,
+        frame_ptr,
+        synthetic.bytes_per_pixel,
+        synthetic.width,
+        synthetic.height,
+        synthetic.stride,
+        synthetic.extension,
+        &mut err,
+    );
+    check_rs2_error!(err, synthetic.could_not_allocate)?;
+
+    let data_ptr = sys::rs2_get_frame_data(new_frame_ptr, &mut err);
+    if let Err(e) = check_rs2_error!(err, synthetic.could_not_allocate) {
+        sys::rs2_release_frame(new_frame_ptr);
+        return Err(e);
+    }
+    ptr::copy_nonoverlapping(
+        synthetic.data.as_ptr(),
+        data_ptr as *mut u8,
+        synthetic.data.len(),
+    );
+
+    // Ownership of the new frame passes to the frame source.
+    sys::rs2_synthetic_frame_ready(source, new_frame_ptr, &mut err);
+    check_rs2_error!(err, synthetic.could_not_allocate)
+}
+
+/// Produce a copy of the frame `frame_ptr` holding the pixel data in `synthetic`.
+///
+/// The copy keeps the stream profile and metadata of the original frame.
+///
+/// # Errors
+///
+/// Returns `could_not_create_block`, `could_not_add_ref` or `could_not_process` if the
+/// processing block producing the copy could not be created or run, the `could_not_allocate`
+/// error of `synthetic` if the copy could not be allocated, and any error that occurs while
+/// waiting for the copy (see [`FrameQueue::wait_for_frame`]).
+unsafe fn synthesize_frame<E>(
+    frame_ptr: NonNull<sys::rs2_frame>,
+    mut synthetic: SyntheticFrame<E>,
+    could_not_create_block: fn(Rs2Exception, String) -> E,
+    could_not_add_ref: fn(Rs2Exception, String) -> E,
+    could_not_process: fn(Rs2Exception, String) -> E,
+) -> Result<AnyFrame>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut queue = FrameQueue::with_capacity(1)?;
+
+    let mut err = ptr::null_mut::<sys::rs2_error>();
+    let block_ptr = sys::rs2_create_processing_block_fptr(
+        Some(synthetic_frame_trampoline::<E>),
+        (&mut synthetic as *mut SyntheticFrame<E>).cast::<c_void>(),
+        &mut err,
+    );
+    check_rs2_error!(err, could_not_create_block)?;
+
+    // Processing blocks invoke their callback on the calling thread, so `synthetic` is no longer
+    // referenced once the block has been deleted.
+    let processed = process_frame(
+        block_ptr,
+        frame_ptr,
+        &queue,
+        could_not_add_ref,
+        could_not_process,
+    );
+    sys::rs2_delete_processing_block(block_ptr);
+    processed?;
+    synthetic.result?;
+
+    queue.wait_for_frame(None)
+}
+
+impl ColorFrame {
+    /// Get the pixel format of the frame data.
+    pub fn format(&self) -> Rs2Format {
+        self.frame_stream_profile.format()
+    }
+
+    /// Copy the frame into an owned buffer of tightly packed RGB8 pixels, in row-major order.
+    ///
+    /// The frame data is converted as needed (see [`PixelKind::to_rgb8`]), so that the result is
+    /// the same regardless of whether the frame was streamed as RGB(A)8, BGR(A)8, YUYV, UYVY or
+    /// grayscale. Any padding at the end of each row is skipped.
+    ///
+    /// Returns an empty buffer if the frame is in a format that cannot be converted to RGB8 (e.g.
+    /// compressed formats such as MJPEG).
+    pub fn to_rgb8_vec(&self) -> Vec<u8> {
+        match self.format() {
+            Rs2Format::Rgb8
+            | Rs2Format::Rgba8
+            | Rs2Format::Bgr8
+            | Rs2Format::Bgra8
+            | Rs2Format::Yuyv
+            | Rs2Format::Uyvy
+            | Rs2Format::Raw8
+            | Rs2Format::Y8
+            | Rs2Format::Y16 => (),
+            _ => return Vec::new(),
+        }
+
+        let mut rgb = Vec::with_capacity(self.width * self.height * 3);
+        for pixel in self.iter() {
+            if let Some(channels) = pixel.to_rgb8() {
+                rgb.extend_from_slice(&channels);
+            }
+        }
+        rgb
+    }
+
+    /// Encode the frame as a JPEG image with the given `quality`.
+    ///
+    /// The frame is first converted to RGB8 (see [`ColorFrame::to_rgb8_vec`]), so that RGB(A)8,
+    /// BGR(A)8, YUYV, UYVY and grayscale frames can all be encoded. `quality` ranges from 1
+    /// (worst) to 100 (best); values outside of that range are clamped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JpegEncodingError::UnsupportedFormat`] if the frame is in a format that cannot be
+    /// converted to RGB8.
+    ///
+    /// Returns [`JpegEncodingError::CouldNotEncode`] if the encoder fails.
+    #[cfg(feature = "jpeg")]
+    pub fn to_jpeg(&self, quality: u8) -> Result<Vec<u8>, JpegEncodingError> {
+        let rgb = self.to_rgb8_vec();
+        if rgb.len() != self.width * self.height * 3 {
+            return Err(JpegEncodingError::UnsupportedFormat(self.format()));
+        }
+
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, quality).encode(
+            &rgb,
+            self.width as u32,
+            self.height as u32,
+            image::ColorType::Rgb8,
+        )?;
+        Ok(jpeg)
+    }
+}
+
+impl ColorFrame {
+    /// Remove the lens distortion from the frame, producing a rectified copy of it.
+    ///
+    /// `intrinsics` must describe the stream of this frame (see
+    /// [`StreamProfile::intrinsics`]). Every pixel of the output is sampled from the position at
+    /// which the lens images it, using nearest-neighbour sampling. Output pixels that are imaged
+    /// outside of the frame are left black. The output keeps the stream profile, format and
+    /// metadata of this frame.
+    ///
+    /// The supported distortion models are [`Rs2DistortionModel::None`] (the frame is copied
+    /// as-is), [`Rs2DistortionModel::BrownConrady`], [`Rs2DistortionModel::BrownConradyModified`]
+    /// and [`Rs2DistortionModel::BrownConradyInverse`]. The supported formats are those that
+    /// store each pixel separately: RGB(A)8, BGR(A)8, Y8 and Y16.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UndistortError::UnsupportedDistortionModel`] or
+    /// [`UndistortError::UnsupportedFormat`] if the distortion model or the format of the frame
+    /// is not supported.
+    ///
+    /// Returns [`UndistortError::IntrinsicsDoNotMatchFrame`] if the resolution of the intrinsics
+    /// differs from that of the frame.
+    ///
+    /// Returns any other [`UndistortError`] if the undistorted frame could not be produced, and
+    /// any error that occurs while waiting for it (see [`FrameQueue::wait_for_frame`]).
+    pub fn undistort(&self, intrinsics: &Rs2Intrinsics) -> Result<ColorFrame> {
+        let distortion = intrinsics.distortion();
+        if distort_point(distortion.model, &distortion.coeffs, 0.0, 0.0).is_none() {
+            return Err(UndistortError::UnsupportedDistortionModel(distortion.model).into());
+        }
+
+        let format = self.format();
+        match format {
+            Rs2Format::Rgb8
+            | Rs2Format::Rgba8
+            | Rs2Format::Bgr8
+            | Rs2Format::Bgra8
+            | Rs2Format::Y8
+            | Rs2Format::Y16 => (),
+            _ => return Err(UndistortError::UnsupportedFormat(format).into()),
+        }
+
+        if intrinsics.width() != self.width || intrinsics.height() != self.height {
+            return Err(UndistortError::IntrinsicsDoNotMatchFrame.into());
+        }
+
+        let bytes_per_pixel = self.bits_per_pixel / BITS_PER_BYTE as usize;
+        let source = unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr().cast::<u8>(), self.data_size_in_bytes)
+        };
+        let data = remap(
+            source,
+            self.width,
+            self.height,
+            self.stride,
+            bytes_per_pixel,
+            |col, row| {
+                let x = (col as f32 - intrinsics.ppx()) / intrinsics.fx();
+                let y = (row as f32 - intrinsics.ppy()) / intrinsics.fy();
+                let (x, y) = distort_point(distortion.model, &distortion.coeffs, x, y)?;
+                Some((
+                    x * intrinsics.fx() + intrinsics.ppx(),
+                    y * intrinsics.fy() + intrinsics.ppy(),
+                ))
+            },
+        );
+
+        let synthetic = SyntheticFrame {
+            data,
+            width: self.width as c_int,
+            height: self.height as c_int,
+            stride: self.stride as c_int,
+            bytes_per_pixel: bytes_per_pixel as c_int,
+            extension: sys::rs2_extension_RS2_EXTENSION_VIDEO_FRAME,
+            could_not_allocate: UndistortError::CouldNotAllocateFrame,
+            result: Ok(()),
+        };
+        let frame = unsafe {
+            synthesize_frame(
+                self.frame_ptr,
+                synthetic,
+                UndistortError::CouldNotCreateProcessingBlock,
+                UndistortError::CouldNotAddFrameReference,
+                UndistortError::CouldNotProcessFrame,
+            )?
+        };
+
+        match frame {
+            AnyFrame::Color(frame) => Ok(frame),
+            _ => Err(UndistortError::DidNotProduceColorFrame.into()),
+        }
+    }
+}
+
+/// Map a normalized, undistorted image point to the normalized point at which the lens images it.
+///
+/// `coeffs` are the distortion coefficients `[k1, k2, p1, p2, k3]` of `model`. Returns `None` if
+/// the distortion model is not supported.
+fn distort_point(
+    model: Rs2DistortionModel,
+    coeffs: &[f32; 5],
+    x: f32,
+    y: f32,
+) -> Option<(f32, f32)> {
+    let [k1, k2, p1, p2, k3] = *coeffs;
+    let radial = |r2: f32| 1.0 + k1 * r2 + k2 * r2 * r2 + k3 * r2 * r2 * r2;
+    let tangential = |x: f32, y: f32, r2: f32| {
+        (
+            2.0 * p1 * x * y + p2 * (r2 + 2.0 * x * x),
+            2.0 * p2 * x * y + p1 * (r2 + 2.0 * y * y),
+        )
+    };
+
+    match model {
+        Rs2DistortionModel::None => Some((x, y)),
+        Rs2DistortionModel::BrownConrady => {
+            let r2 = x * x + y * y;
+            let f = radial(r2);
+            let (dx, dy) = tangential(x, y, r2);
+            Some((x * f + dx, y * f + dy))
+        }
+        Rs2DistortionModel::BrownConradyModified => {
+            // As in librealsense2, the tangential terms are evaluated at the radially distorted
+            // point.
+            let r2 = x * x + y * y;
+            let f = radial(r2);
+            let (x, y) = (x * f, y * f);
+            let (dx, dy) = tangential(x, y, r2);
+            Some((x + dx, y + dy))
+        }
+        Rs2DistortionModel::BrownConradyInverse => {
+            // The coefficients map distorted points to undistorted ones, so the mapping has to be
+            // inverted, which is done by fixed-point iteration.
+            let (mut xd, mut yd) = (x, y);
+            for _ in 0..INVERSE_DISTORTION_ITERATIONS {
+                let r2 = xd * xd + yd * yd;
+                let f = radial(r2);
+                let (dx, dy) = tangential(xd, yd, r2);
+                xd = (x - dx) / f;
+                yd = (y - dy) / f;
+            }
+            Some((xd, yd))
+        }
+        _ => None,
+    }
+}
+
+/// Build an image with the same layout as `source`, where the pixel at `(col, row)` is copied
+/// from the source pixel nearest to `map(col, row)`.
+///
+/// Pixels which `map` sends outside of the image, or for which it returns `None`, are zeroed.
+fn remap(
+    source: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    bytes_per_pixel: usize,
+    map: impl Fn(usize, usize) -> Option<(f32, f32)>,
+) -> Vec<u8> {
+    let mut output = vec![0; stride * height];
+
+    for row in 0..height {
+        for col in 0..width {
+            let (src_col, src_row) = match map(col, row) {
+                Some((x, y)) => (x.round(), y.round()),
+                None => continue,
+            };
+            if !(0.0..width as f32).contains(&src_col) || !(0.0..height as f32).contains(&src_row) {
+                continue;
+            }
+
+            let from = src_row as usize * stride + src_col as usize * bytes_per_pixel;
+            let to = row * stride + col * bytes_per_pixel;
+            if let Some(pixel) = source.get(from..from + bytes_per_pixel) {
+                output[to..to + bytes_per_pixel].copy_from_slice(pixel);
+            }
+        }
+    }
+    output
 }
 
 impl<K> ImageFrame<K> {
@@ -533,6 +1313,53 @@ impl<K> ImageFrame<K> {
         }
     }
 
+    /// Iterate over the rows of the frame as byte slices, without copying.
+    ///
+    /// Each row holds the `width * bits_per_pixel / 8` bytes of its pixels (rounded up to whole
+    /// bytes). Any padding at the end of a row, as given by the [stride](ImageFrame::stride), is
+    /// skipped.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        let data = self.data_bytes();
+        let stride = self.stride;
+        let row_size = self.row_size();
+
+        // The frame was checked to hold enough data for every row on construction.
+        (0..self.height).map(move |row| &data[row * stride..row * stride + row_size])
+    }
+
+    /// Expand the packed samples of a [`Rs2Format::Raw10`] frame into one `u16` per pixel, in
+    /// row-major order.
+    ///
+    /// librealsense2 packs each group of four 10-bit samples into five bytes: the first four
+    /// bytes hold the upper 8 bits of the four samples, and the fifth byte holds their lower 2
+    /// bits, starting with the first sample in the least significant bits. Any padding at the end
+    /// of each row is skipped, so the buffer holds exactly `width * height` values.
+    ///
+    /// Returns `None` if the frame is not in the [`Rs2Format::Raw10`] format.
+    pub fn unpack_raw10(&self) -> Option<Vec<u16>> {
+        if self.frame_stream_profile.format() != Rs2Format::Raw10 {
+            return None;
+        }
+
+        let mut samples = Vec::with_capacity(self.width * self.height);
+        for row in self.rows() {
+            unpack_raw10_row(row, self.width, &mut samples);
+        }
+        Some(samples)
+    }
+
+    /// Get the data of the frame as a byte slice, including any padding.
+    fn data_bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr().cast::<u8>(), self.data_size_in_bytes)
+        }
+    }
+
+    /// Get the number of bytes holding the pixels of a single row, excluding padding.
+    fn row_size(&self) -> usize {
+        (self.width * self.bits_per_pixel).div_ceil(BITS_PER_BYTE as usize)
+    }
+
     /// Get the stride of this Video frame's pixel in bytes.
     pub fn stride(&self) -> usize {
         self.stride
@@ -580,6 +1407,199 @@ impl<K> ImageFrame<K> {
     }
 }
 
+impl<K> ImageFrame<K>
+where
+    ImageFrame<K>: FrameCategory,
+{
+    /// Produce a copy of the region of interest of this frame.
+    ///
+    /// The bounds of `roi` are inclusive, so e.g. a region from `(0, 0)` to `(9, 4)` produces a
+    /// frame of 10 by 5 pixels. The pixel data is copied into a new software-backed frame of the
+    /// same type and format, with tightly packed rows. The cropped frame keeps the stream
+    /// profile (including the intrinsics) and metadata of this frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CropError::UnsupportedFormat`] if the format of the frame does not have a whole
+    /// number of bytes per pixel, e.g. compressed or bit-packed formats.
+    ///
+    /// Returns [`CropError::InvalidRegionOfInterest`] if the region is empty or exceeds the bounds
+    /// of the frame. For [`Rs2Format::Yuyv`] and [`Rs2Format::Uyvy`] frames, which share the
+    /// chroma of each pair of pixels, the region must also start and end on a pixel pair.
+    ///
+    /// Returns any other [`CropError`] if the cropped frame could not be produced, and any error
+    /// that occurs while waiting for it (see [`FrameQueue::wait_for_frame`]).
+    pub fn crop(&self, roi: Rs2Roi) -> Result<ImageFrame<K>> {
+        let format = self.frame_stream_profile.format();
+        let bytes_per_pixel = match format.bytes_per_pixel() {
+            Some(bytes) if bytes * BITS_PER_BYTE as usize == self.bits_per_pixel => bytes,
+            _ => return Err(CropError::UnsupportedFormat(format).into()),
+        };
+
+        let (cols, rows) = crop_bounds(&roi, self.width, self.height)
+            .ok_or_else(|| CropError::InvalidRegionOfInterest(roi.clone()))?;
+        let is_pixel_pair_format = matches!(format, Rs2Format::Yuyv | Rs2Format::Uyvy);
+        if is_pixel_pair_format && (cols.start % 2 != 0 || cols.len() % 2 != 0) {
+            return Err(CropError::InvalidRegionOfInterest(roi).into());
+        }
+
+        let width = cols.len();
+        let height = rows.len();
+        let col_bytes = cols.start * bytes_per_pixel..cols.end * bytes_per_pixel;
+        let data = crop_bytes(self.data_bytes(), self.stride, col_bytes, rows);
+
+        #[allow(clippy::useless_conversion)]
+        let extension = (Self::extension() as i32).try_into().unwrap();
+        let synthetic = SyntheticFrame {
+            data,
+            width: width as c_int,
+            height: height as c_int,
+            stride: (width * bytes_per_pixel) as c_int,
+            bytes_per_pixel: bytes_per_pixel as c_int,
+            extension,
+            could_not_allocate: CropError::CouldNotAllocateFrame,
+            result: Ok(()),
+        };
+        let frame = unsafe {
+            synthesize_frame(
+                self.frame_ptr,
+                synthetic,
+                CropError::CouldNotCreateProcessingBlock,
+                CropError::CouldNotAddFrameReference,
+                CropError::CouldNotProcessFrame,
+            )?
+        };
+
+        frame
+            .into_raw_for_queue()
+            .try_extract()
+            .ok_or_else(|| CropError::DidNotProduceFrame.into())
+    }
+}
+
+/// Replace every zero in `row` by the nearest non-zero value, preferring the left one on ties.
+///
+/// A row holding only zeros is left as-is.
+fn fill_row_holes(row: &mut [u16]) {
+    // The column of the nearest non-zero value at or to the right of every column.
+    let mut right = vec![None; row.len()];
+    let mut next = None;
+    for col in (0..row.len()).rev() {
+        if row[col] != 0 {
+            next = Some(col);
+        }
+        right[col] = next;
+    }
+
+    let mut left: Option<(usize, u16)> = None;
+    for col in 0..row.len() {
+        if row[col] != 0 {
+            left = Some((col, row[col]));
+            continue;
+        }
+
+        row[col] = match (left, right[col]) {
+            (Some((l, _)), Some(r)) if r - col < col - l => row[r],
+            (Some((_, depth)), _) => depth,
+            (None, Some(r)) => row[r],
+            (None, None) => continue,
+        };
+    }
+}
+
+/// Get the columns and rows covered by the inclusive bounds of `roi` in a `width` by `height`
+/// image.
+///
+/// Returns `None` if the region is empty or exceeds the bounds of the image.
+fn crop_bounds(roi: &Rs2Roi, width: usize, height: usize) -> Option<(Range<usize>, Range<usize>)> {
+    let min_x = usize::try_from(roi.min_x).ok()?;
+    let min_y = usize::try_from(roi.min_y).ok()?;
+    let max_x = usize::try_from(roi.max_x).ok()?;
+    let max_y = usize::try_from(roi.max_y).ok()?;
+
+    if min_x > max_x || min_y > max_y || max_x >= width || max_y >= height {
+        return None;
+    }
+    Some((min_x..max_x + 1, min_y..max_y + 1))
+}
+
+/// Copy the bytes `col_bytes` of each of the rows `rows` of an image with `stride` bytes per row
+/// into a tightly packed buffer.
+fn crop_bytes(data: &[u8], stride: usize, col_bytes: Range<usize>, rows: Range<usize>) -> Vec<u8> {
+    let mut cropped = Vec::with_capacity(col_bytes.len() * rows.len());
+    for row in rows {
+        let start = row * stride;
+        cropped.extend_from_slice(&data[start + col_bytes.start..start + col_bytes.end]);
+    }
+    cropped
+}
+
+/// Expand the first `width` packed 10-bit samples of `row` and append them to `samples`.
+///
+/// See [`ImageFrame::unpack_raw10`] for the packing layout.
+fn unpack_raw10_row(row: &[u8], width: usize, samples: &mut Vec<u16>) {
+    let unpacked = row.chunks(5).flat_map(|group| {
+        let low_bits = group.get(4).copied().unwrap_or(0);
+        group.iter().take(4).enumerate().map(move |(i, high_bits)| {
+            (u16::from(*high_bits) << 2) | u16::from((low_bits >> (2 * i)) & 0b11)
+        })
+    });
+    samples.extend(unpacked.take(width));
+}
+
+/// Copy every `factor`-th value of every `factor`-th row of a `width` by `height` image.
+///
+/// Returns the `(width, height, values)` of the decimated image.
+fn decimate<'a, T: Copy + 'a>(
+    rows: impl Iterator<Item = &'a [T]>,
+    width: usize,
+    height: usize,
+    factor: usize,
+) -> (usize, usize, Vec<T>) {
+    let factor = factor.max(1);
+    let new_width = width.div_ceil(factor);
+    let new_height = height.div_ceil(factor);
+
+    let mut values = Vec::with_capacity(new_width * new_height);
+    for row in rows.step_by(factor) {
+        values.extend(row.iter().step_by(factor).copied());
+    }
+
+    (new_width, new_height, values)
+}
+
+#[cfg(feature = "rayon")]
+impl<K> ImageFrame<K> {
+    /// Parallel iterator through every [pixel](crate::frame::PixelKind) of an image frame.
+    ///
+    /// The frame is split into rows which are processed in parallel. Each item is a tuple of
+    /// `(col, row, pixel)`. Pixels are not guaranteed to be yielded in row-major order.
+    pub fn par_pixels(&self) -> impl ParallelIterator<Item = (usize, usize, PixelKind<'_>)> + '_ {
+        let format = self.frame_stream_profile.format();
+        let data_size_in_bytes = self.data_size_in_bytes;
+        let stride = self.stride;
+        let width = self.width;
+        // The frame data is read-only for the lifetime of the frame, so it is safe to read it from
+        // several threads at once. The pointer is passed as an address since raw pointers are not
+        // `Send`.
+        let data = self.data.as_ptr() as usize;
+
+        (0..self.height).into_par_iter().flat_map_iter(move |row| {
+            (0..width).map(move |col| unsafe {
+                let pixel = get_pixel(
+                    format,
+                    data_size_in_bytes,
+                    data as *const std::os::raw::c_void,
+                    stride,
+                    col,
+                    row,
+                );
+                (col, row, pixel)
+            })
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -588,9 +1608,114 @@ mod tests {
     fn frame_has_correct_kind() {
         assert_eq!(ColorFrame::kind(), Rs2StreamKind::Color);
         assert_eq!(DepthFrame::kind(), Rs2StreamKind::Depth);
+        assert_eq!(DistanceFrame::kind(), Rs2StreamKind::Depth);
         assert_eq!(DisparityFrame::kind(), Rs2StreamKind::Any);
         assert_eq!(InfraredFrame::kind(), Rs2StreamKind::Infrared);
         assert_eq!(FisheyeFrame::kind(), Rs2StreamKind::Fisheye);
         assert_eq!(ConfidenceFrame::kind(), Rs2StreamKind::Confidence);
     }
+
+    #[test]
+    fn decimation_skips_rows_and_columns() {
+        let image: Vec<u16> = (0..15).collect();
+        let rows = || image.chunks(5);
+
+        assert_eq!(decimate(rows(), 5, 3, 1), (5, 3, image.clone()));
+        assert_eq!(decimate(rows(), 5, 3, 0), (5, 3, image.clone()));
+        assert_eq!(decimate(rows(), 5, 3, 2), (3, 2, vec![0, 2, 4, 10, 12, 14]));
+        assert_eq!(decimate(rows(), 5, 3, 5), (1, 1, vec![0]));
+    }
+
+    #[test]
+    fn holes_are_filled_from_the_nearest_valid_pixel() {
+        let mut row = [0, 5, 0, 0, 0, 9, 0, 0];
+        fill_row_holes(&mut row);
+        assert_eq!(row, [5, 5, 5, 5, 9, 9, 9, 9]);
+
+        let mut row = [0, 0, 0];
+        fill_row_holes(&mut row);
+        assert_eq!(row, [0, 0, 0]);
+    }
+
+    #[test]
+    fn crop_copies_the_region_of_interest() {
+        let roi = |min_x, min_y, max_x, max_y| Rs2Roi {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        };
+        assert_eq!(crop_bounds(&roi(1, 0, 2, 1), 4, 3), Some((1..3, 0..2)));
+        assert_eq!(crop_bounds(&roi(1, 0, 4, 1), 4, 3), None);
+        assert_eq!(crop_bounds(&roi(2, 0, 1, 1), 4, 3), None);
+        assert_eq!(crop_bounds(&roi(-1, 0, 1, 1), 4, 3), None);
+
+        // Three rows of four two-byte pixels, padded to a stride of 10 bytes.
+        let data: Vec<u8> = (0..30).collect();
+        assert_eq!(
+            crop_bytes(&data, 10, 2..6, 1..3),
+            vec![12, 13, 14, 15, 22, 23, 24, 25]
+        );
+    }
+
+    #[test]
+    fn raw10_samples_are_unpacked() {
+        let row = [0xFF, 0x00, 0x80, 0x01, 0b1110_0100, 0x02, 0x03];
+        let mut samples = Vec::new();
+
+        unpack_raw10_row(&row, 5, &mut samples);
+        assert_eq!(samples, vec![0x3FC, 0x001, 0x202, 0x007, 0x008]);
+    }
+
+    #[test]
+    fn undistorting_without_distortion_copies_the_image() {
+        let source: Vec<u8> = (0..18).collect();
+        let identity = |col: usize, row: usize| Some((col as f32, row as f32));
+        assert_eq!(remap(&source, 3, 2, 9, 3, identity), source);
+
+        let shifted = |col: usize, row: usize| Some((col as f32 + 1.0, row as f32));
+        let output = remap(&source, 3, 2, 9, 3, shifted);
+        assert_eq!(output[0..6], source[3..9]);
+        assert_eq!(output[6..9], [0; 3]);
+    }
+
+    #[test]
+    fn inverse_brown_conrady_undoes_its_coefficients() {
+        let coeffs = [0.1, -0.05, 0.001, -0.002, 0.01];
+        let (x, y) = (0.3, -0.2);
+
+        // With the inverse model, the coefficients map the distorted point to the undistorted one.
+        let (ux, uy) = distort_point(Rs2DistortionModel::BrownConrady, &coeffs, x, y).unwrap();
+        let (dx, dy) =
+            distort_point(Rs2DistortionModel::BrownConradyInverse, &coeffs, ux, uy).unwrap();
+        assert!((dx - x).abs() < 1e-4 && (dy - y).abs() < 1e-4);
+
+        assert_eq!(
+            distort_point(Rs2DistortionModel::None, &coeffs, x, y),
+            Some((x, y))
+        );
+        assert_eq!(
+            distort_point(Rs2DistortionModel::KannalaBrandt, &coeffs, x, y),
+            None
+        );
+    }
+
+    #[test]
+    fn required_data_size_is_checked() {
+        assert_eq!(required_data_size(640, 480, 16, 1280), Some(640 * 480 * 2));
+        // Padded rows, where the last row lacks the padding.
+        assert_eq!(
+            required_data_size(640, 480, 16, 1344),
+            Some(1344 * 479 + 1280)
+        );
+        assert_eq!(required_data_size(640, 0, 16, 1280), Some(0));
+        assert_eq!(required_data_size(-1, 480, 16, 1280), None);
+        assert_eq!(required_data_size(640, 480, 16, -1), None);
+        // The size can only overflow if `usize` is no wider than `c_int`.
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(
+            required_data_size(c_int::MAX, c_int::MAX, c_int::MAX, c_int::MAX),
+            None
+        );
+    }
 }
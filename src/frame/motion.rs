@@ -5,8 +5,12 @@
 //!
 //! See the docs for [MotionFrame::motion] for more.
 
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+use super::prelude::{
+    add_frame_ref, frame_data, CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError,
+    FrameEx,
+};
 use crate::{
+    base::Rs2MotionDeviceIntrinsics,
     check_rs2_error,
     kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
     sensor::Sensor,
@@ -102,6 +106,27 @@ impl<K> Drop for MotionFrame<K> {
     }
 }
 
+/// Cloning a frame adds a reference to the underlying librealsense2 frame rather than copying its
+/// data. Each clone releases its own reference when dropped.
+impl<K> Clone for MotionFrame<K> {
+    fn clone(&self) -> Self {
+        unsafe {
+            add_frame_ref(self.frame_ptr);
+        }
+
+        Self {
+            frame_ptr: self.frame_ptr,
+            timestamp: self.timestamp,
+            timestamp_domain: self.timestamp_domain,
+            frame_number: self.frame_number,
+            frame_stream_profile: self.frame_stream_profile.share(),
+            motion: self.motion,
+            should_drop: true,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 unsafe impl<K> Send for MotionFrame<K> {}
 
 impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
@@ -120,6 +145,8 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
     /// - [CouldNotGetFrameStreamProfile](FrameConstructionError::CouldNotGetFrameStreamProfile)
     /// - [CouldNotGetDataSize](FrameConstructionError::CouldNotGetDataSize)
     /// - [CouldNotGetData](FrameConstructionError::CouldNotGetData)
+    /// - [UnexpectedFormat](FrameConstructionError::UnexpectedFormat)
+    /// - [DataSizeMismatch](FrameConstructionError::DataSizeMismatch)
     ///
     /// See [FrameConstructionError] documentation for more details.
     ///
@@ -144,6 +171,10 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
                 NonNull::new(profile_ptr as *mut sys::rs2_stream_profile).unwrap();
             let profile = StreamProfile::try_from(nonnull_profile_ptr)?;
 
+            if !profile.format().is_motion() {
+                return Err(FrameConstructionError::UnexpectedFormat(profile.format()).into());
+            }
+
             let size = sys::rs2_get_frame_data_size(frame_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, FrameConstructionError::CouldNotGetDataSize)?;
 
@@ -153,6 +184,14 @@ impl<K> TryFrom<NonNull<sys::rs2_frame>> for MotionFrame<K> {
             let data_as_ptr = ptr.as_ref().unwrap() as *const std::os::raw::c_void;
             let data_size_in_f32s = (size as usize) / std::mem::size_of::<f32>();
 
+            if data_size_in_f32s < 3 {
+                return Err(FrameConstructionError::DataSizeMismatch {
+                    expected: 3 * std::mem::size_of::<f32>(),
+                    actual: size as usize,
+                }
+                .into());
+            }
+
             let motion_raw =
                 std::slice::from_raw_parts(data_as_ptr.cast::<f32>(), data_size_in_f32s);
 
@@ -239,6 +278,16 @@ impl<K> FrameEx for MotionFrame<K> {
         }
     }
 
+    fn keep(&self) {
+        unsafe {
+            sys::rs2_keep_frame(self.frame_ptr.as_ptr());
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        unsafe { frame_data(self.frame_ptr) }
+    }
+
     unsafe fn get_owned_raw(mut self) -> NonNull<sys::rs2_frame> {
         self.should_drop = false;
 
@@ -246,6 +295,19 @@ impl<K> FrameEx for MotionFrame<K> {
     }
 }
 
+impl<K> MotionFrame<K> {
+    /// Returns the motion data of the frame, corrected with the given motion intrinsics.
+    ///
+    /// `intrinsics` must describe the stream of this frame (see
+    /// [`StreamProfile::motion_intrinsics`]). The data is corrected the same way librealsense2
+    /// corrects it: it is multiplied with the 3x3 scale and cross-axis matrix of the intrinsics,
+    /// and the bias is subtracted afterwards (see [`Rs2MotionDeviceIntrinsics::data`]).
+    ///
+    pub fn corrected_data(&self, intrinsics: &Rs2MotionDeviceIntrinsics) -> [f32; 3] {
+        correct_motion(&intrinsics.data(), &self.motion)
+    }
+}
+
 impl AccelFrame {
     /// Returns a 3-item array representing the sensor motion recorded in the Accel frame.
     ///
@@ -302,6 +364,15 @@ impl GyroFrame {
     }
 }
 
+/// Apply the 3x4 scale and bias matrix `data` of a motion device to the motion data `raw`.
+fn correct_motion(data: &[[f32; 4]; 3], raw: &[f32; 3]) -> [f32; 3] {
+    let mut corrected = [0.0; 3];
+    for (value, row) in corrected.iter_mut().zip(data) {
+        *value = row[0] * raw[0] + row[1] * raw[1] + row[2] * raw[2] - row[3];
+    }
+    corrected
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -311,4 +382,14 @@ mod tests {
         assert_eq!(AccelFrame::kind(), Rs2StreamKind::Accel);
         assert_eq!(GyroFrame::kind(), Rs2StreamKind::Gyro);
     }
+
+    #[test]
+    fn motion_is_scaled_before_removing_bias() {
+        let data = [
+            [2.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.5, 0.0],
+            [0.0, 0.0, 1.0, -1.0],
+        ];
+        assert_eq!(correct_motion(&data, &[1.0, 2.0, 4.0]), [1.0, 4.0, 5.0]);
+    }
 }
@@ -110,6 +110,66 @@ pub enum PixelKind<'a> {
     },
 }
 
+impl<'a> PixelKind<'a> {
+    /// Get the format of the frame data the pixel was read from.
+    pub fn format(&self) -> Rs2Format {
+        match self {
+            PixelKind::Yuyv { .. } => Rs2Format::Yuyv,
+            PixelKind::Uyvy { .. } => Rs2Format::Uyvy,
+            PixelKind::Bgr8 { .. } => Rs2Format::Bgr8,
+            PixelKind::Bgra8 { .. } => Rs2Format::Bgra8,
+            PixelKind::Rgb8 { .. } => Rs2Format::Rgb8,
+            PixelKind::Rgba8 { .. } => Rs2Format::Rgba8,
+            PixelKind::Raw8 { .. } => Rs2Format::Raw8,
+            PixelKind::Y8 { .. } => Rs2Format::Y8,
+            PixelKind::Y16 { .. } => Rs2Format::Y16,
+            PixelKind::Z16 { .. } => Rs2Format::Z16,
+            PixelKind::Distance { .. } => Rs2Format::Distance,
+            PixelKind::Disparity32 { .. } => Rs2Format::Disparity32,
+            PixelKind::Xyz32f { .. } => Rs2Format::Xyz32F,
+        }
+    }
+
+    /// Convert the pixel to an 8-bit RGB triplet.
+    ///
+    /// YUYV / UYVY pixels are converted using the same (BT.601) coefficients that librealsense2
+    /// uses internally, BGR(A) pixels have their channels reordered, and grayscale pixels (Y8, Y16,
+    /// Raw8) are replicated across all three channels. Alpha channels are dropped.
+    ///
+    /// Returns `None` for depth, distance, disparity and point formats, for which an RGB value is
+    /// not meaningful.
+    pub fn to_rgb8(&self) -> Option<[u8; 3]> {
+        match self {
+            PixelKind::Yuyv { y, u, v } | PixelKind::Uyvy { y, u, v } => {
+                Some(yuv_to_rgb8(**y, **u, **v))
+            }
+            PixelKind::Bgr8 { b, g, r } | PixelKind::Bgra8 { b, g, r, .. } => Some([**r, **g, **b]),
+            PixelKind::Rgb8 { r, g, b } | PixelKind::Rgba8 { r, g, b, .. } => Some([**r, **g, **b]),
+            PixelKind::Raw8 { val: y } | PixelKind::Y8 { y } => Some([**y; 3]),
+            PixelKind::Y16 { y } => Some([(**y >> 8) as u8; 3]),
+            PixelKind::Z16 { .. }
+            | PixelKind::Distance { .. }
+            | PixelKind::Disparity32 { .. }
+            | PixelKind::Xyz32f { .. } => None,
+        }
+    }
+}
+
+/// Convert a single YUV value to RGB8, using the BT.601 coefficients used by librealsense2.
+fn yuv_to_rgb8(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = y as i32 - 16;
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+
+    let clamp = |val: i32| val.clamp(0, u8::MAX as i32) as u8;
+
+    [
+        clamp((298 * c + 409 * e + 128) >> 8),
+        clamp((298 * c - 100 * d - 208 * e + 128) >> 8),
+        clamp((298 * c + 516 * d + 128) >> 8),
+    ]
+}
+
 /// Method to retrieve a pixel from a given rs2_frame in the requested Pixel format.
 ///
 /// # Safety
@@ -215,7 +275,7 @@ pub(crate) unsafe fn get_pixel<'a>(
             let slice = slice::from_raw_parts(data.cast::<u8>(), data_size_in_bytes);
             let offset = (row * stride_in_bytes) + (col * 3);
 
-            PixelKind::Bgr8 {
+            PixelKind::Rgb8 {
                 r: slice.get_unchecked(offset),
                 g: slice.get_unchecked(offset + 1),
                 b: slice.get_unchecked(offset + 2),
@@ -227,7 +287,7 @@ pub(crate) unsafe fn get_pixel<'a>(
             let slice = slice::from_raw_parts(data.cast::<u8>(), data_size_in_bytes);
             let offset = (row * stride_in_bytes) + (col * 4);
 
-            PixelKind::Bgra8 {
+            PixelKind::Rgba8 {
                 r: slice.get_unchecked(offset),
                 g: slice.get_unchecked(offset + 1),
                 b: slice.get_unchecked(offset + 2),
@@ -290,11 +350,12 @@ pub(crate) unsafe fn get_pixel<'a>(
                 disparity: slice.get_unchecked(offset),
             }
         }
+        // Each XYZ32F pixel holds three consecutive f32 values.
         Rs2Format::Xyz32F => {
             let size = data_size_in_bytes / std::mem::size_of::<f32>();
             let stride = stride_in_bytes / std::mem::size_of::<f32>();
             let slice = slice::from_raw_parts(data.cast::<f32>(), size);
-            let offset = (row * stride) + col;
+            let offset = (row * stride) + (col * 3);
 
             PixelKind::Xyz32f {
                 x: slice.get_unchecked(offset),
@@ -307,3 +368,127 @@ pub(crate) unsafe fn get_pixel<'a>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_supported_format_has_its_own_variant() {
+        let supported = [
+            Rs2Format::Yuyv,
+            Rs2Format::Uyvy,
+            Rs2Format::Bgr8,
+            Rs2Format::Bgra8,
+            Rs2Format::Rgb8,
+            Rs2Format::Rgba8,
+            Rs2Format::Raw8,
+            Rs2Format::Y8,
+            Rs2Format::Y16,
+            Rs2Format::Z16,
+            Rs2Format::Distance,
+            Rs2Format::Disparity32,
+            Rs2Format::Xyz32F,
+        ];
+        // Use u32s to keep the buffer aligned for every pixel type.
+        let data = [0u32; 16];
+        let size = std::mem::size_of_val(&data);
+
+        for format in supported.iter() {
+            let pixel = unsafe { get_pixel(*format, size, data.as_ptr().cast(), size, 0, 0) };
+            assert_eq!(pixel.format(), *format);
+        }
+    }
+
+    #[test]
+    fn xyz_pixels_hold_three_values() {
+        // Two rows of two pixels each.
+        let data: Vec<f32> = (0..12).map(|value| value as f32).collect();
+        let size = std::mem::size_of_val(data.as_slice());
+        let stride = size / 2;
+
+        let pixel =
+            unsafe { get_pixel(Rs2Format::Xyz32F, size, data.as_ptr().cast(), stride, 1, 0) };
+        match pixel {
+            PixelKind::Xyz32f { x, y, z } => assert_eq!([*x, *y, *z], [3.0, 4.0, 5.0]),
+            _ => panic!("expected an XYZ32F pixel"),
+        }
+
+        let pixel =
+            unsafe { get_pixel(Rs2Format::Xyz32F, size, data.as_ptr().cast(), stride, 1, 1) };
+        match pixel {
+            PixelKind::Xyz32f { x, y, z } => assert_eq!([*x, *y, *z], [9.0, 10.0, 11.0]),
+            _ => panic!("expected an XYZ32F pixel"),
+        }
+    }
+
+    #[test]
+    fn bgr_is_reordered_to_rgb() {
+        let (b, g, r, a) = (1, 2, 3, 255);
+        assert_eq!(
+            PixelKind::Bgr8 {
+                b: &b,
+                g: &g,
+                r: &r
+            }
+            .to_rgb8(),
+            Some([3, 2, 1])
+        );
+        assert_eq!(
+            PixelKind::Bgra8 {
+                b: &b,
+                g: &g,
+                r: &r,
+                a: &a
+            }
+            .to_rgb8(),
+            Some([3, 2, 1])
+        );
+        assert_eq!(
+            PixelKind::Rgb8 {
+                r: &r,
+                g: &g,
+                b: &b
+            }
+            .to_rgb8(),
+            Some([3, 2, 1])
+        );
+    }
+
+    #[test]
+    fn yuv_is_converted_to_rgb() {
+        let (black, white, neutral) = (16, 235, 128);
+        assert_eq!(
+            PixelKind::Yuyv {
+                y: &black,
+                u: &neutral,
+                v: &neutral
+            }
+            .to_rgb8(),
+            Some([0, 0, 0])
+        );
+        assert_eq!(
+            PixelKind::Uyvy {
+                y: &white,
+                u: &neutral,
+                v: &neutral
+            }
+            .to_rgb8(),
+            Some([255, 255, 255])
+        );
+    }
+
+    #[test]
+    fn depth_has_no_rgb() {
+        let depth = 1000;
+        let distance = 1.0;
+        assert_eq!(PixelKind::Z16 { depth: &depth }.to_rgb8(), None);
+        assert_eq!(
+            PixelKind::Distance {
+                distance: &distance
+            }
+            .to_rgb8(),
+            None
+        );
+    }
+}
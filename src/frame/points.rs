@@ -2,8 +2,12 @@
 //!
 //! A Points frame is a RealSense point cloud storage class.
 
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+use super::prelude::{
+    add_frame_ref, frame_data, is_frame_extendable_to, CouldNotGetFrameSensorError, FrameCategory,
+    FrameConstructionError, FrameEx,
+};
 use crate::{
+    base::Rs2Extrinsics,
     check_rs2_error,
     kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
     sensor::Sensor,
@@ -57,8 +61,10 @@ impl FrameCategory for PointsFrame {
         Rs2StreamKind::Any
     }
 
+    /// Points frames can come from any stream, so rather than comparing the stream kind this
+    /// checks that the frame actually is a points frame.
     fn has_correct_kind(&self) -> bool {
-        self.frame_stream_profile.kind() == Self::kind()
+        unsafe { is_frame_extendable_to(self.frame_ptr, Self::extension()) }
     }
 }
 
@@ -133,6 +139,16 @@ impl FrameEx for PointsFrame {
         }
     }
 
+    fn keep(&self) {
+        unsafe {
+            sys::rs2_keep_frame(self.frame_ptr.as_ptr());
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        unsafe { frame_data(self.frame_ptr) }
+    }
+
     unsafe fn get_owned_raw(mut self) -> NonNull<sys::rs2_frame> {
         self.should_drop = false;
 
@@ -153,6 +169,28 @@ impl Drop for PointsFrame {
     }
 }
 
+/// Cloning a frame adds a reference to the underlying librealsense2 frame rather than copying its
+/// data. Each clone releases its own reference when dropped.
+impl Clone for PointsFrame {
+    fn clone(&self) -> Self {
+        unsafe {
+            add_frame_ref(self.frame_ptr);
+        }
+
+        Self {
+            frame_ptr: self.frame_ptr,
+            timestamp: self.timestamp,
+            timestamp_domain: self.timestamp_domain,
+            frame_number: self.frame_number,
+            frame_stream_profile: self.frame_stream_profile.share(),
+            num_points: self.num_points,
+            vertices_data_ptr: self.vertices_data_ptr,
+            texture_data_ptr: self.texture_data_ptr,
+            should_drop: true,
+        }
+    }
+}
+
 unsafe impl Send for PointsFrame {}
 
 impl std::convert::TryFrom<NonNull<sys::rs2_frame>> for PointsFrame {
@@ -253,6 +291,62 @@ impl PointsFrame {
     pub fn points_count(&self) -> usize {
         self.num_points
     }
+
+    /// Iterate over the vertices of the point cloud that correspond to a valid depth reading.
+    ///
+    /// Vertices with a depth (z) of zero or less, e.g. where no depth could be computed, are
+    /// skipped.
+    pub fn valid_vertices(&self) -> impl Iterator<Item = [f32; 3]> + '_ {
+        self.vertices()
+            .iter()
+            .map(|vertex| vertex.xyz)
+            .filter(is_valid_vertex)
+    }
+
+    /// Iterate over the vertices of the point cloud that correspond to a valid depth reading,
+    /// together with their texture coordinates.
+    ///
+    /// See [`PointsFrame::valid_vertices`] for which vertices are skipped, and
+    /// [`PointsFrame::texture_coordinates`] for how the texture coordinates are read.
+    pub fn valid_vertices_with_uv(&self) -> impl Iterator<Item = ([f32; 3], [f32; 2])> + '_ {
+        self.vertices()
+            .iter()
+            .zip(self.texture_coordinates())
+            .map(|(vertex, uv)| (vertex.xyz, *uv))
+            .filter(|(xyz, _)| is_valid_vertex(xyz))
+    }
+
+    /// Gets the vertices of the point cloud, transformed by the given extrinsics.
+    ///
+    /// Each vertex is rotated and then translated, as done by `rs2_transform_point_to_point`, e.g.
+    /// to bring the point clouds of two cameras into the same coordinate system. Vertices that are
+    /// all zero do not correspond to a valid depth reading, and are skipped. The returned vector
+    /// can thus be shorter than [`PointsFrame::points_count`].
+    pub fn transformed(&self, extrinsics: &Rs2Extrinsics) -> Vec<[f32; 3]> {
+        let rotation = extrinsics.rotation();
+        let translation = extrinsics.translation();
+
+        self.vertices()
+            .iter()
+            .filter(|vertex| vertex.xyz != [0.0; 3])
+            .map(|vertex| transform_point(&rotation, &translation, vertex.xyz))
+            .collect()
+    }
+}
+
+/// Predicate for whether a vertex corresponds to a valid depth reading.
+fn is_valid_vertex(xyz: &[f32; 3]) -> bool {
+    xyz[2] > 0.0
+}
+
+/// Apply a rigid transform with a column-major `rotation` matrix and a `translation` to `point`.
+fn transform_point(rotation: &[f32; 9], translation: &[f32; 3], point: [f32; 3]) -> [f32; 3] {
+    let [x, y, z] = point;
+    [
+        rotation[0] * x + rotation[3] * y + rotation[6] * z + translation[0],
+        rotation[1] * x + rotation[4] * y + rotation[7] * z + translation[1],
+        rotation[2] * x + rotation[5] * y + rotation[8] * z + translation[2],
+    ]
 }
 
 #[cfg(test)]
@@ -263,4 +357,23 @@ mod tests {
     fn frame_has_correct_kind() {
         assert_eq!(PointsFrame::kind(), Rs2StreamKind::Any);
     }
+
+    #[test]
+    fn vertices_without_depth_are_invalid() {
+        assert!(is_valid_vertex(&[0.1, -0.2, 0.5]));
+        assert!(!is_valid_vertex(&[0.0, 0.0, 0.0]));
+        assert!(!is_valid_vertex(&[0.1, 0.2, -0.5]));
+    }
+
+    #[test]
+    fn transform_point_rotates_then_translates() {
+        // A rotation of 90 degrees about the z axis, in column-major order.
+        let rotation = [0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        let translation = [1.0, 2.0, 3.0];
+
+        assert_eq!(
+            transform_point(&rotation, &translation, [1.0, 0.0, 0.5]),
+            [1.0, 3.0, 3.5]
+        );
+    }
 }
@@ -4,7 +4,10 @@
 //! at a point in time. See the member and function declarations for how these values are stored
 //! and retrieved.
 
-use super::prelude::{CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError, FrameEx};
+use super::prelude::{
+    add_frame_ref, frame_data, CouldNotGetFrameSensorError, FrameCategory, FrameConstructionError,
+    FrameEx,
+};
 use crate::{
     check_rs2_error,
     kind::{Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
@@ -125,6 +128,26 @@ impl Drop for PoseFrame {
     }
 }
 
+/// Cloning a frame adds a reference to the underlying librealsense2 frame rather than copying its
+/// data. Each clone releases its own reference when dropped.
+impl Clone for PoseFrame {
+    fn clone(&self) -> Self {
+        unsafe {
+            add_frame_ref(self.frame_ptr);
+        }
+
+        Self {
+            frame_ptr: self.frame_ptr,
+            timestamp: self.timestamp,
+            timestamp_domain: self.timestamp_domain,
+            frame_number: self.frame_number,
+            frame_stream_profile: self.frame_stream_profile.share(),
+            data: self.data,
+            should_drop: true,
+        }
+    }
+}
+
 unsafe impl Send for PoseFrame {}
 
 impl FrameCategory for PoseFrame {
@@ -269,6 +292,16 @@ impl FrameEx for PoseFrame {
         }
     }
 
+    fn keep(&self) {
+        unsafe {
+            sys::rs2_keep_frame(self.frame_ptr.as_ptr());
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        unsafe { frame_data(self.frame_ptr) }
+    }
+
     unsafe fn get_owned_raw(mut self) -> NonNull<sys::rs2_frame> {
         self.should_drop = false;
 
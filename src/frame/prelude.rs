@@ -4,19 +4,118 @@
 //! different functionalities. These are encapsulated by the `*FrameEx` traits,
 //! with the wildcard describing the specialization that goes with that type.
 
+use super::raw::RawFrame;
 use crate::{
-    kind::{Rs2Exception, Rs2Extension, Rs2FrameMetadata, Rs2StreamKind, Rs2TimestampDomain},
+    base::Rs2Roi,
+    kind::{
+        Rs2DistortionModel, Rs2Exception, Rs2Extension, Rs2Format, Rs2FrameMetadata, Rs2StreamKind,
+        Rs2TimestampDomain,
+    },
     sensor::Sensor,
-    stream_profile::StreamProfile,
+    stream_profile::{StreamConstructionError, StreamProfile},
 };
 use anyhow::Result;
 use realsense_sys as sys;
-use std::ptr::NonNull;
+use std::{
+    convert::{TryFrom, TryInto},
+    ptr::NonNull,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use thiserror::Error;
 
 /// How many bits are in a byte? Who can truly say.
 pub const BITS_PER_BYTE: i32 = 8;
 
+/// The offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash a sequence of byte chunks with the 64-bit FNV-1a hash, as if they were one buffer.
+///
+/// FNV-1a is used for [`FrameEx::content_hash`] since it is fast, and fully specified, so that
+/// hashes are stable across runs, platforms and compiler versions.
+pub(crate) fn fnv1a<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    chunks
+        .into_iter()
+        .flatten()
+        .fold(FNV_OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// Predicate for whether the frame behind `frame_ptr` is extendable to `extension`.
+///
+/// Returns `false` if librealsense2 could not check the extension.
+///
+/// # Safety
+///
+/// `frame_ptr` must point to a valid frame.
+pub(crate) unsafe fn is_frame_extendable_to(
+    frame_ptr: NonNull<sys::rs2_frame>,
+    extension: Rs2Extension,
+) -> bool {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let is_extendable_to = sys::rs2_is_frame_extendable_to(
+        frame_ptr.as_ptr(),
+        #[allow(clippy::useless_conversion)]
+        (extension as i32).try_into().unwrap(),
+        &mut err,
+    );
+
+    if err.as_ref().is_none() {
+        is_extendable_to != 0
+    } else {
+        sys::rs2_free_error(err);
+        false
+    }
+}
+
+/// Add a reference to the frame behind `frame_ptr`, which must be released separately.
+///
+/// This backs the `Clone` implementations of the frame types, which hand the same frame to
+/// several owners without copying the frame data.
+///
+/// # Safety
+///
+/// `frame_ptr` must point to a valid frame.
+pub(crate) unsafe fn add_frame_ref(frame_ptr: NonNull<sys::rs2_frame>) {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    sys::rs2_frame_add_ref(frame_ptr.as_ptr(), &mut err);
+
+    // librealsense2 only raises an error here if the frame pointer is null.
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+    }
+}
+
+/// Get the entire data buffer of a frame, see [`FrameEx::data`].
+///
+/// Returns an empty slice if the data of the frame cannot be obtained.
+///
+/// # Safety
+///
+/// `frame_ptr` must point to a valid frame, which outlives the returned slice.
+pub(crate) unsafe fn frame_data<'a>(frame_ptr: NonNull<sys::rs2_frame>) -> &'a [u8] {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let size = sys::rs2_get_frame_data_size(frame_ptr.as_ptr(), &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return &[];
+    }
+
+    let data = sys::rs2_get_frame_data(frame_ptr.as_ptr(), &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return &[];
+    }
+
+    match (NonNull::new(data as *mut u8), usize::try_from(size)) {
+        (Some(data), Ok(size)) => std::slice::from_raw_parts(data.as_ptr(), size),
+        _ => &[],
+    }
+}
+
 /// Occurs when a frame type cannot be constructed from the given data.
 #[derive(Error, Debug)]
 pub enum FrameConstructionError {
@@ -54,6 +153,35 @@ pub enum FrameConstructionError {
     /// Could not get the number of points in a Points frame.
     #[error("Could not get number of points: Type: {0}; Reason: {1}")]
     CouldNotGetPointCount(Rs2Exception, String),
+    /// The frame dimensions are negative, or too large to compute the size of the frame data.
+    #[error("Invalid frame dimensions. Width: {width}; Height: {height}; Bits per pixel: {bits_per_pixel}")]
+    InvalidDimensions {
+        /// The width of the frame in pixels.
+        width: i32,
+        /// The height of the frame in pixels.
+        height: i32,
+        /// The number of bits per pixel.
+        bits_per_pixel: i32,
+    },
+    /// The frame is not of the expected type, e.g. a single frame where a frameset was expected.
+    #[error("Frame is not extendable to {0:?}.")]
+    NotExtendableTo(Rs2Extension),
+    /// The format of the frame cannot be interpreted by the frame type, e.g. a motion frame whose
+    /// stream profile does not describe motion data.
+    #[error("Unexpected frame format: {0:?}")]
+    UnexpectedFormat(Rs2Format),
+    /// The size of the frame data does not match the size expected from the frame dimensions.
+    ///
+    /// Image frames may hold more data than expected (e.g. padding after the last row), but not
+    /// less.
+    #[error("Frame data size mismatch. Expected: {expected} bytes; Actual: {actual} bytes")]
+    DataSizeMismatch {
+        /// The data size in bytes expected from the frame dimensions, or for image frames, the
+        /// minimum data size.
+        expected: usize,
+        /// The data size in bytes reported by the frame.
+        actual: usize,
+    },
 }
 
 /// Occurs when certain data cannot be derived from a Depth frame.
@@ -72,6 +200,113 @@ pub enum DepthError {
 #[error("Could not get baseline. Type: {0}; Reason: {1}")]
 pub struct DisparityError(pub Rs2Exception, pub String);
 
+/// Occurs when a Disparity frame cannot be converted to a Depth frame.
+#[derive(Error, Debug)]
+pub enum DisparityConversionError {
+    /// Could not create the disparity transform processing block.
+    #[error("Could not create disparity transform. Type: {0}; Reason: {1}")]
+    CouldNotCreateTransform(Rs2Exception, String),
+    /// Could not add a reference to the frame before handing it to the processing block.
+    #[error("Could not add frame reference. Type: {0}; Reason: {1}")]
+    CouldNotAddFrameReference(Rs2Exception, String),
+    /// Could not start the processing block or process the frame.
+    #[error("Could not process frame. Type: {0}; Reason: {1}")]
+    CouldNotProcessFrame(Rs2Exception, String),
+    /// The processing block produced a frame that is not a Depth frame.
+    #[error("Disparity transform did not produce a depth frame.")]
+    DidNotProduceDepthFrame,
+}
+
+/// Occurs when a Color frame cannot be undistorted.
+#[derive(Error, Debug)]
+pub enum UndistortError {
+    /// The distortion model of the intrinsics is not supported.
+    #[error("Cannot undistort frames with distortion model {0:?}.")]
+    UnsupportedDistortionModel(Rs2DistortionModel),
+    /// The pixel format of the frame is not supported.
+    #[error("Cannot undistort frames in format {0:?}.")]
+    UnsupportedFormat(Rs2Format),
+    /// The resolution of the intrinsics does not match the resolution of the frame.
+    #[error("Intrinsics do not match the resolution of the frame.")]
+    IntrinsicsDoNotMatchFrame,
+    /// Could not create the processing block which produces the undistorted frame.
+    #[error("Could not create processing block. Type: {0}; Reason: {1}")]
+    CouldNotCreateProcessingBlock(Rs2Exception, String),
+    /// Could not add a reference to the frame before handing it to the processing block.
+    #[error("Could not add frame reference. Type: {0}; Reason: {1}")]
+    CouldNotAddFrameReference(Rs2Exception, String),
+    /// Could not start the processing block or process the frame.
+    #[error("Could not process frame. Type: {0}; Reason: {1}")]
+    CouldNotProcessFrame(Rs2Exception, String),
+    /// Could not allocate or fill the undistorted frame.
+    #[error("Could not allocate frame. Type: {0}; Reason: {1}")]
+    CouldNotAllocateFrame(Rs2Exception, String),
+    /// The processing block produced a frame that is not a Color frame.
+    #[error("Undistortion did not produce a color frame.")]
+    DidNotProduceColorFrame,
+}
+
+/// Occurs when a Color frame cannot be encoded as a JPEG image.
+#[cfg(feature = "jpeg")]
+#[derive(Error, Debug)]
+pub enum JpegEncodingError {
+    /// The pixel format of the frame cannot be converted to RGB8.
+    #[error("Cannot encode frames in format {0:?} as JPEG.")]
+    UnsupportedFormat(Rs2Format),
+    /// The JPEG encoder failed.
+    #[error("Could not encode frame as JPEG: {0}")]
+    CouldNotEncode(#[from] image::ImageError),
+}
+
+/// Occurs when the holes of a Depth frame cannot be filled.
+#[derive(Error, Debug)]
+pub enum HoleFillingError {
+    /// The pixel format of the frame is not supported.
+    #[error("Cannot fill holes of frames in format {0:?}.")]
+    UnsupportedFormat(Rs2Format),
+    /// Could not create the processing block which produces the filled frame.
+    #[error("Could not create processing block. Type: {0}; Reason: {1}")]
+    CouldNotCreateProcessingBlock(Rs2Exception, String),
+    /// Could not add a reference to the frame before handing it to the processing block.
+    #[error("Could not add frame reference. Type: {0}; Reason: {1}")]
+    CouldNotAddFrameReference(Rs2Exception, String),
+    /// Could not start the processing block or process the frame.
+    #[error("Could not process frame. Type: {0}; Reason: {1}")]
+    CouldNotProcessFrame(Rs2Exception, String),
+    /// Could not allocate or fill the filled frame.
+    #[error("Could not allocate frame. Type: {0}; Reason: {1}")]
+    CouldNotAllocateFrame(Rs2Exception, String),
+    /// The processing block produced a frame that is not a Depth frame.
+    #[error("Hole filling did not produce a depth frame.")]
+    DidNotProduceDepthFrame,
+}
+
+/// Occurs when an Image frame cannot be cropped.
+#[derive(Error, Debug)]
+pub enum CropError {
+    /// The pixel format of the frame is not supported.
+    #[error("Cannot crop frames in format {0:?}.")]
+    UnsupportedFormat(Rs2Format),
+    /// The region of interest is empty, exceeds the bounds of the frame, or splits pixel pairs.
+    #[error("Invalid region of interest: {0:?}")]
+    InvalidRegionOfInterest(Rs2Roi),
+    /// Could not create the processing block which produces the cropped frame.
+    #[error("Could not create processing block. Type: {0}; Reason: {1}")]
+    CouldNotCreateProcessingBlock(Rs2Exception, String),
+    /// Could not add a reference to the frame before handing it to the processing block.
+    #[error("Could not add frame reference. Type: {0}; Reason: {1}")]
+    CouldNotAddFrameReference(Rs2Exception, String),
+    /// Could not start the processing block or process the frame.
+    #[error("Could not process frame. Type: {0}; Reason: {1}")]
+    CouldNotProcessFrame(Rs2Exception, String),
+    /// Could not allocate or fill the cropped frame.
+    #[error("Could not allocate frame. Type: {0}; Reason: {1}")]
+    CouldNotAllocateFrame(Rs2Exception, String),
+    /// The processing block produced a frame of a different type than the cropped frame.
+    #[error("Cropping did not produce a frame of the same type.")]
+    DidNotProduceFrame,
+}
+
 /// Cannot get the frame sensor.
 #[derive(Error, Debug)]
 #[error("Could not get frame sensor. Type: {0}; Reason: {1}")]
@@ -82,18 +317,120 @@ pub trait FrameEx {
     /// Get the stream profile associated with the frame.
     fn stream_profile(&self) -> &StreamProfile;
 
+    /// Get an owned copy of the stream profile associated with the frame.
+    ///
+    /// Unlike [`stream_profile`](FrameEx::stream_profile), the returned profile does not borrow
+    /// from the frame, so it (and e.g. its intrinsics) can be kept after the frame is dropped. See
+    /// [`StreamProfile::try_clone`] for how the copy is made.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StreamConstructionError`] if the stream profile could not be cloned.
+    fn stream_profile_owned(&self) -> Result<StreamProfile, StreamConstructionError> {
+        self.stream_profile().try_clone()
+    }
+
+    /// Get the stream kind of the frame, e.g. Depth or Color.
+    ///
+    /// Shortcut for `frame.stream_profile().kind()`. The kind is read once when the stream profile
+    /// of the frame is constructed, so this does not call into librealsense2.
+    fn stream_kind(&self) -> Rs2StreamKind {
+        self.stream_profile().kind()
+    }
+
+    /// Get the format of the frame data.
+    ///
+    /// Shortcut for `frame.stream_profile().format()`. Like [`stream_kind`](FrameEx::stream_kind),
+    /// this does not call into librealsense2.
+    fn format(&self) -> Rs2Format {
+        self.stream_profile().format()
+    }
+
     /// Get the sensor associated with the frame.
     fn sensor(&self) -> Result<Sensor>;
 
+    /// Try to get the sensor associated with the frame.
+    ///
+    /// Frames produced by processing blocks (e.g. the output of a colorizer) are not associated
+    /// with any sensor. Returns `None` in that case, rather than the error that
+    /// [`sensor`](FrameEx::sensor) would return.
+    fn try_sensor(&self) -> Option<Sensor> {
+        self.sensor().ok()
+    }
+
     /// Get the frame number.
     fn frame_number(&self) -> u64;
 
     /// Get the frame timestamp.
+    ///
+    /// The timestamp is in milliseconds, relative to the clock described by
+    /// [`timestamp_domain`](FrameEx::timestamp_domain). Timestamps of frames in different domains
+    /// cannot be compared, and subtracting them is meaningless; see
+    /// [`timestamp_comparable_with`](FrameEx::timestamp_comparable_with).
     fn timestamp(&self) -> f64;
 
     /// Get the RealSense timestamp domain for the current timestamp.
     fn timestamp_domain(&self) -> Rs2TimestampDomain;
 
+    /// Predicate for whether the timestamps of this frame and `other` can be compared, i.e.
+    /// whether both are in the same [timestamp domain](FrameEx::timestamp_domain).
+    ///
+    /// E.g. the timestamp of a depth frame in the
+    /// [`HardwareClock`](Rs2TimestampDomain::HardwareClock) domain cannot be compared with that
+    /// of a color frame in the [`SystemTime`](Rs2TimestampDomain::SystemTime) domain. Check this
+    /// before subtracting the timestamps of frames from different streams or devices.
+    fn timestamp_comparable_with(&self, other: &dyn FrameEx) -> bool {
+        self.timestamp_domain() == other.timestamp_domain()
+    }
+
+    /// Get the frame timestamp as a [`Duration`].
+    ///
+    /// The raw timestamp is reported by librealsense2 in milliseconds, relative to the clock
+    /// described by [`timestamp_domain`](FrameEx::timestamp_domain). Negative timestamps are
+    /// clamped to zero.
+    fn timestamp_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.timestamp().max(0.0) / 1000.0)
+    }
+
+    /// Get the frame timestamp as a host [`SystemTime`].
+    ///
+    /// Returns `None` if the timestamp domain is [`Rs2TimestampDomain::HardwareClock`], since
+    /// there is no mapping from the device clock to the host clock. Timestamps in the
+    /// [`SystemTime`](Rs2TimestampDomain::SystemTime) and
+    /// [`GlobalTime`](Rs2TimestampDomain::GlobalTime) domains are both relative to the UNIX epoch
+    /// on the host.
+    fn system_time(&self) -> Option<SystemTime> {
+        match self.timestamp_domain() {
+            Rs2TimestampDomain::HardwareClock => None,
+            Rs2TimestampDomain::SystemTime | Rs2TimestampDomain::GlobalTime => {
+                UNIX_EPOCH.checked_add(self.timestamp_duration())
+            }
+        }
+    }
+
+    /// Get the time of the middle of the sensor's exposure for this frame.
+    ///
+    /// The [`SensorTimestamp`](Rs2FrameMetadata::SensorTimestamp) metadata is computed by the
+    /// device clock in microseconds, whereas [`timestamp`](FrameEx::timestamp) usually marks when
+    /// the frame was transmitted. Returns `None` if the metadata is not supported by the frame,
+    /// or if it is negative.
+    fn sensor_timestamp(&self) -> Option<Duration> {
+        self.metadata(Rs2FrameMetadata::SensorTimestamp)
+            .and_then(|usec| u64::try_from(usec).ok())
+            .map(Duration::from_micros)
+    }
+
+    /// Get the time at which the frame was received by the host backend (e.g. the UVC driver).
+    ///
+    /// The [`BackendTimestamp`](Rs2FrameMetadata::BackendTimestamp) metadata is reported in
+    /// microseconds. Returns `None` if the metadata is not supported by the frame, or if it is
+    /// negative.
+    fn backend_timestamp(&self) -> Option<Duration> {
+        self.metadata(Rs2FrameMetadata::BackendTimestamp)
+            .and_then(|usec| u64::try_from(usec).ok())
+            .map(Duration::from_micros)
+    }
+
     /// Get frame metadata.
     ///
     /// Returns `None` if the `metadata_kind` is not supported by the frame type.
@@ -102,6 +439,72 @@ pub trait FrameEx {
     /// Test whether the metadata arguemnt is supported by the frame.
     fn supports_metadata(&self, metadata_kind: Rs2FrameMetadata) -> bool;
 
+    /// Get frame metadata, or `default` if the `metadata_kind` is not supported by the frame.
+    fn metadata_or(&self, metadata_kind: Rs2FrameMetadata, default: i64) -> i64 {
+        self.metadata(metadata_kind).unwrap_or(default)
+    }
+
+    /// Get the actual framerate of the stream at the time the frame was captured, in frames per
+    /// second.
+    ///
+    /// librealsense2 reports the [`ActualFps`](Rs2FrameMetadata::ActualFps) metadata in
+    /// thousandths of a frame per second, which is converted here. Returns `None` if the metadata
+    /// is not supported by the frame.
+    fn actual_fps(&self) -> Option<f64> {
+        self.metadata(Rs2FrameMetadata::ActualFps)
+            .map(|fps| fps as f64 / 1000.0)
+    }
+
+    /// Get the exposure time of the sensor for this frame, in microseconds.
+    ///
+    /// Returns `None` if the [`ActualExposure`](Rs2FrameMetadata::ActualExposure) metadata is not
+    /// supported by the frame.
+    fn exposure_usec(&self) -> Option<i64> {
+        self.metadata(Rs2FrameMetadata::ActualExposure)
+    }
+
+    /// Get the relative gain level of the sensor for this frame.
+    ///
+    /// Returns `None` if the [`GainLevel`](Rs2FrameMetadata::GainLevel) metadata is not supported
+    /// by the frame.
+    fn gain_level(&self) -> Option<i64> {
+        self.metadata(Rs2FrameMetadata::GainLevel)
+    }
+
+    /// Keep the frame alive beyond the callback it was delivered to.
+    ///
+    /// librealsense2 allocates frames from a fixed-size pool per stream, and recycles a frame as
+    /// soon as it is released. Frames that are held onto for longer than a callback (e.g. to
+    /// buffer them for batch processing later) should be kept with this method, which removes the
+    /// frame from the pool's accounting.
+    ///
+    /// Kept frames are no longer recycled, so the SDK can no longer guarantee that no
+    /// allocations occur while streaming. Keeping many frames (or keeping frames indefinitely)
+    /// increases memory usage accordingly.
+    fn keep(&self);
+
+    /// Get the data held by the frame as raw bytes.
+    ///
+    /// This is the entire data buffer as allocated by librealsense2, e.g. the pixels of an image
+    /// frame including any padding at the end of each row, or the vertices followed by the
+    /// texture coordinates of a points frame. The bytes have to be interpreted according to the
+    /// [format](FrameEx::format) of the frame. Returns an empty slice if the data cannot be
+    /// obtained.
+    fn data(&self) -> &[u8];
+
+    /// Compute a hash of the data held by the frame.
+    ///
+    /// The hash only depends on the frame data (e.g. the pixels of an image frame, without any
+    /// padding at the end of each row), not on the frame number, timestamp or metadata. It is
+    /// computed with a fast, non-cryptographic hash that is stable across runs, which makes it
+    /// useful to check in tests that the output of some processing did not change.
+    ///
+    /// The default implementation hashes [`FrameEx::data`], which frame types whose data contains
+    /// padding override.
+    fn content_hash(&self) -> u64 {
+        fnv1a(Some(self.data()))
+    }
+
     /// Get (and own) the underlying frame pointer for this frame.
     ///
     /// This is primarily useful for passing this frame forward to a processing block or blocks
@@ -113,6 +516,18 @@ pub trait FrameEx {
     /// goes out of scope. Instead, the program expects that whatever
     /// object was assigned to by this function now manages the lifetime.
     unsafe fn get_owned_raw(self) -> NonNull<sys::rs2_frame>;
+
+    /// Transfer ownership of the frame into a [`RawFrame`], e.g. to hand it to a frame queue or
+    /// processing block.
+    ///
+    /// Unlike [`get_owned_raw`](FrameEx::get_owned_raw), the frame is still released exactly once,
+    /// either when the [`RawFrame`] is dropped or by whoever it hands ownership on to.
+    fn into_raw_for_queue(self) -> RawFrame
+    where
+        Self: Sized,
+    {
+        unsafe { RawFrame::from_queued_raw(self.get_owned_raw()) }
+    }
 }
 
 /// A trait for specifying which runtime stream kinds can be held within a frame type
@@ -138,3 +553,18 @@ pub trait FrameCategory {
     /// Predicate for checking if the RS2 frame's stream has the same kind as the frame category.
     fn has_correct_kind(&self) -> bool;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_matches_reference_values() {
+        assert_eq!(fnv1a(None), FNV_OFFSET_BASIS);
+        assert_eq!(fnv1a(Some(&b"a"[..])), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            fnv1a([&b"foo"[..], &b"bar"[..]]),
+            fnv1a(Some(&b"foobar"[..]))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Type for buffering frames in a thread-safe queue.
+//!
+//! A [`FrameQueue`] wraps librealsense2's `rs2_frame_queue`, which can be used to hand frames from
+//! one thread to another, or to decouple frame acquisition from frame processing. When the queue
+//! is full, the oldest frames are dropped to make room for new ones.
+
+use super::{any::AnyFrame, prelude::FrameEx, raw::RawFrame};
+use crate::{check_rs2_error, kind::Rs2Exception};
+use anyhow::Result;
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use realsense_sys as sys;
+use std::{os::raw::c_void, ptr::NonNull, time::Duration};
+use thiserror::Error;
+
+/// Type describing errors that can occur when creating or reading from a frame queue.
+///
+/// Follows the standard pattern of errors where the enum variant describes what the low-level code
+/// was attempting to do while the string carried alongside describes the underlying error message
+/// from any C++ exceptions that occur.
+#[derive(Error, Debug)]
+pub enum FrameQueueError {
+    /// Could not create the frame queue.
+    #[error("Could not create frame queue. Type: {0}; Reason: {1}")]
+    CouldNotCreateFrameQueue(Rs2Exception, String),
+    /// librealsense2 had an internal error occur while waiting for a frame.
+    #[error("An internal error occurred while waiting for a frame. Type: {0}; Reason: {1}")]
+    CouldNotWaitForFrame(Rs2Exception, String),
+    /// The queue timed out while waiting for a frame.
+    #[error("Timed out while waiting for frame.")]
+    DidTimeoutBeforeFrameArrival,
+}
+
+/// A queue of frames, holding up to a fixed number of frames at a time.
+#[derive(Debug)]
+pub struct FrameQueue {
+    /// A (non-null) pointer to the frame queue.
+    queue_ptr: NonNull<sys::rs2_frame_queue>,
+}
+
+impl Drop for FrameQueue {
+    fn drop(&mut self) {
+        unsafe {
+            sys::rs2_delete_frame_queue(self.queue_ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for FrameQueue {}
+
+impl FrameQueue {
+    /// Create a new frame queue which holds at most `capacity` frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotCreateFrameQueue`] if the queue could not be created.
+    pub fn with_capacity(capacity: usize) -> Result<Self, FrameQueueError> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let queue_ptr = sys::rs2_create_frame_queue(capacity as i32, &mut err);
+            check_rs2_error!(err, FrameQueueError::CouldNotCreateFrameQueue)?;
+
+            Ok(Self {
+                queue_ptr: NonNull::new(queue_ptr).unwrap(),
+            })
+        }
+    }
+
+    /// Get the number of frames currently held by the queue.
+    pub fn len(&self) -> usize {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let size = sys::rs2_frame_queue_size(self.queue_ptr.as_ptr(), &mut err);
+
+            if err.as_ref().is_none() {
+                size as usize
+            } else {
+                sys::rs2_free_error(err);
+                0
+            }
+        }
+    }
+
+    /// Get the underlying low-level pointer to the frame queue.
+    ///
+    /// # Safety
+    ///
+    /// This method is not intended to be called or used outside of the crate itself. Be warned, it
+    /// is _undefined behaviour_ to call [`realsense_sys::rs2_delete_frame_queue`] on this pointer.
+    /// If you do, you risk a double-free error when the [`FrameQueue`] struct itself is dropped.
+    pub(crate) unsafe fn get_raw(&self) -> NonNull<sys::rs2_frame_queue> {
+        self.queue_ptr
+    }
+
+    /// Predicate for whether the queue currently holds no frames.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Push a frame onto the queue.
+    ///
+    /// Ownership of the frame is moved into the queue. If the queue is already full, the oldest
+    /// frame in the queue is dropped.
+    pub fn enqueue<F: FrameEx>(&mut self, frame: F) {
+        unsafe {
+            sys::rs2_enqueue_frame(
+                frame.into_raw_for_queue().into_raw().as_ptr(),
+                self.queue_ptr.as_ptr().cast::<c_void>(),
+            );
+        }
+    }
+
+    /// Waits for a frame to become available in the queue, blocking the calling thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum duration to wait for a frame. If `None` is passed in, the
+    ///   [default timeout](crate::base::DEFAULT_TIMEOUT) is applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameQueueError::CouldNotWaitForFrame`] if an internal error occurs while
+    /// waiting for a frame.
+    ///
+    /// Returns [`FrameQueueError::DidTimeoutBeforeFrameArrival`] if no frame arrives within
+    /// `timeout`.
+    ///
+    /// Returns any error from [`AnyFrame::from_raw`] if the dequeued frame could not be
+    /// constructed. The frame is released in that case.
+    pub fn wait_for_frame(&mut self, timeout: Option<Duration>) -> Result<AnyFrame> {
+        let timeout_ms = match timeout {
+            Some(d) => d.as_millis() as u32,
+            None => sys::RS2_DEFAULT_TIMEOUT,
+        };
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut frame_ptr = std::ptr::null_mut::<sys::rs2_frame>();
+
+            // See `ActivePipeline::wait` for why the `try_` variant is used here: it lets us
+            // tell a timeout apart from other errors without parsing the error message.
+            let did_get_frame = sys::rs2_try_wait_for_frame(
+                self.queue_ptr.as_ptr(),
+                timeout_ms,
+                &mut frame_ptr,
+                &mut err,
+            );
+            check_rs2_error!(err, FrameQueueError::CouldNotWaitForFrame)?;
+
+            if did_get_frame != 0 {
+                RawFrame::from_queued_raw(NonNull::new(frame_ptr).unwrap()).into_frame()
+            } else {
+                Err(FrameQueueError::DidTimeoutBeforeFrameArrival.into())
+            }
+        }
+    }
+
+    /// Poll the queue for a frame without blocking.
+    ///
+    /// Returns `None` if no frame is immediately available, if an internal error occurs, or if the
+    /// dequeued frame could not be constructed (in which case it is released).
+    pub fn poll_for_frame(&mut self) -> Option<AnyFrame> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let mut frame_ptr = std::ptr::null_mut::<sys::rs2_frame>();
+            let did_get_frame =
+                sys::rs2_poll_for_frame(self.queue_ptr.as_ptr(), &mut frame_ptr, &mut err);
+
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return None;
+            }
+
+            if did_get_frame != 0 {
+                RawFrame::from_queued_raw(NonNull::new(frame_ptr)?)
+                    .into_frame()
+                    .ok()
+            } else {
+                None
+            }
+        }
+    }
+}
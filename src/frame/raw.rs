@@ -0,0 +1,105 @@
+//! Type for transferring ownership of frames across the FFI boundary.
+//!
+//! Frames in librealsense2 are reference counted, and every reference must be released exactly
+//! once. The frame types in this crate release their reference when dropped, unless ownership of
+//! the underlying pointer has been taken with
+//! [`FrameEx::get_owned_raw`](super::FrameEx::get_owned_raw). From that point on, it is up to the
+//! caller to release the frame, which is easy to get wrong when handing frames to and from
+//! processing blocks or frame queues.
+//!
+//! The [`RawFrame`] type holds such a reference without knowing the type of the frame, and
+//! releases it when dropped unless ownership is handed on to librealsense2 or to a typed frame.
+
+use super::prelude::{is_frame_extendable_to, FrameCategory};
+use realsense_sys as sys;
+use std::{convert::TryFrom, ptr::NonNull};
+
+/// An owned reference to a frame of unknown type.
+///
+/// The reference is released exactly once: either when the `RawFrame` is dropped, or by whoever
+/// ownership is transferred to via [`RawFrame::into_raw`] or [`RawFrame::into_frame`].
+#[derive(Debug)]
+pub struct RawFrame {
+    /// The owned frame pointer.
+    frame_ptr: NonNull<sys::rs2_frame>,
+}
+
+impl Drop for RawFrame {
+    fn drop(&mut self) {
+        unsafe {
+            sys::rs2_release_frame(self.frame_ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for RawFrame {}
+
+impl RawFrame {
+    /// Take ownership of a frame pointer, e.g. one dequeued from a frame queue or received from a
+    /// processing block.
+    ///
+    /// # Safety
+    ///
+    /// `frame_ptr` must be a valid frame reference that is owned by the caller, and must not be
+    /// released by anything else afterwards.
+    pub unsafe fn from_queued_raw(frame_ptr: NonNull<sys::rs2_frame>) -> Self {
+        Self { frame_ptr }
+    }
+
+    /// Get the underlying frame pointer, without transferring ownership.
+    pub fn as_ptr(&self) -> NonNull<sys::rs2_frame> {
+        self.frame_ptr
+    }
+
+    /// Give up ownership of the frame pointer, e.g. to hand it to a librealsense2 function which
+    /// takes ownership of the frame it is given (such as `rs2_process_frame`).
+    ///
+    /// The frame is no longer released when this value goes out of scope.
+    pub fn into_raw(self) -> NonNull<sys::rs2_frame> {
+        let frame_ptr = self.frame_ptr;
+        std::mem::forget(self);
+        frame_ptr
+    }
+
+    /// Convert the frame into a typed frame, which takes over ownership of the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error raised while constructing the typed frame, e.g. if the frame is of a
+    /// different type. The frame is released in that case.
+    pub fn into_frame<F>(self) -> Result<F, F::Error>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>>,
+    {
+        // On failure the typed frame was never constructed, so ownership remains with `self`,
+        // which releases the frame when it is dropped.
+        let frame = F::try_from(self.frame_ptr)?;
+        std::mem::forget(self);
+        Ok(frame)
+    }
+
+    /// Convert the frame into a typed frame, if it actually is a frame of that type.
+    ///
+    /// Unlike [`RawFrame::into_frame`], this first checks that the frame is extendable to
+    /// [`F::extension()`](FrameCategory::extension), and afterwards that it
+    /// [has the correct kind](FrameCategory::has_correct_kind). E.g. a color frame is never
+    /// constructed as a [`DepthFrame`](super::DepthFrame), even though both are video frames.
+    ///
+    /// Returns `None` if the frame is not of type `F` or could not be constructed. The frame is
+    /// released in that case.
+    pub fn try_extract<F>(self) -> Option<F>
+    where
+        F: TryFrom<NonNull<sys::rs2_frame>> + FrameCategory,
+    {
+        if !unsafe { is_frame_extendable_to(self.frame_ptr, F::extension()) } {
+            return None;
+        }
+
+        let frame: F = self.into_frame().ok()?;
+        if frame.has_correct_kind() {
+            Some(frame)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Type for merging frames of several streams into a single timeline.
+//!
+//! Frames of different streams are delivered independently of each other, so e.g. a color frame
+//! may arrive after a depth frame that was captured later. The [`ReorderBuffer`] holds on to
+//! frames until no earlier frame is expected anymore, and then emits them in timestamp order.
+
+use super::prelude::FrameEx;
+use crate::kind::Rs2TimestampDomain;
+use std::{collections::VecDeque, time::Duration};
+
+/// Buffers frames and emits them in timestamp order.
+///
+/// A frame is held until a frame whose timestamp is at least the window later has been pushed.
+/// Frames that arrive less than the window late are therefore emitted in order, while frames that
+/// arrive even later are emitted as soon as possible, i.e. out of order.
+///
+/// Timestamps are only comparable within a timestamp domain, so all frames in the buffer must be
+/// in the domain the buffer was constructed with.
+#[derive(Debug, Clone)]
+pub struct ReorderBuffer<F> {
+    /// The timestamp domain of the buffered frames.
+    domain: Rs2TimestampDomain,
+    /// The time frames are held for, in milliseconds.
+    window_ms: f64,
+    /// The latest timestamp pushed so far, in milliseconds.
+    latest_ms: Option<f64>,
+    /// The buffered frames and their timestamps in milliseconds, in timestamp order.
+    frames: VecDeque<(f64, F)>,
+}
+
+impl<F> ReorderBuffer<F> {
+    /// Construct a new, empty buffer for frames in the given timestamp domain, which holds frames
+    /// for `window`.
+    pub fn new(domain: Rs2TimestampDomain, window: Duration) -> Self {
+        Self {
+            domain,
+            window_ms: window.as_secs_f64() * 1000.0,
+            latest_ms: None,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Get the timestamp domain of the buffered frames.
+    pub fn domain(&self) -> Rs2TimestampDomain {
+        self.domain
+    }
+
+    /// Get the number of frames currently held by the buffer.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Predicate for whether the buffer currently holds no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Add a frame with the given timestamp in milliseconds to the buffer.
+    ///
+    /// Frames with equal timestamps are emitted in the order they were inserted.
+    pub fn insert(&mut self, timestamp_ms: f64, frame: F) {
+        let index = self.frames.partition_point(|(t, _)| *t <= timestamp_ms);
+        self.frames.insert(index, (timestamp_ms, frame));
+
+        self.latest_ms = Some(match self.latest_ms {
+            Some(latest_ms) => latest_ms.max(timestamp_ms),
+            None => timestamp_ms,
+        });
+    }
+
+    /// Remove the earliest frame from the buffer, if it has been held for the window.
+    ///
+    /// Returns `None` if the buffer is empty, or if an earlier frame may still arrive.
+    pub fn pop_ready(&mut self) -> Option<F> {
+        let latest_ms = self.latest_ms?;
+        let (timestamp_ms, _) = self.frames.front()?;
+
+        if latest_ms - timestamp_ms >= self.window_ms {
+            self.frames.pop_front().map(|(_, frame)| frame)
+        } else {
+            None
+        }
+    }
+
+    /// Remove all frames from the buffer, regardless of how long they have been held.
+    ///
+    /// Returns the frames in timestamp order. This is e.g. useful once the streams have been
+    /// stopped, as no more frames are expected.
+    pub fn flush(&mut self) -> Vec<F> {
+        self.latest_ms = None;
+        self.frames.drain(..).map(|(_, frame)| frame).collect()
+    }
+}
+
+impl<F: FrameEx> ReorderBuffer<F> {
+    /// Add a frame to the buffer, using its timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns the frame back if it is not in the timestamp domain of this buffer.
+    pub fn push(&mut self, frame: F) -> Result<(), F> {
+        if frame.timestamp_domain() != self.domain {
+            return Err(frame);
+        }
+
+        self.insert(frame.timestamp(), frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_emitted_in_timestamp_order() {
+        let mut buffer =
+            ReorderBuffer::new(Rs2TimestampDomain::HardwareClock, Duration::from_millis(10));
+
+        buffer.insert(100.0, "depth 1");
+        buffer.insert(95.0, "color 1");
+        assert_eq!(buffer.pop_ready(), None);
+
+        buffer.insert(105.0, "accel 1");
+        assert_eq!(buffer.pop_ready(), Some("color 1"));
+        assert_eq!(buffer.pop_ready(), None);
+
+        buffer.insert(110.0, "depth 2");
+        assert_eq!(buffer.pop_ready(), Some("depth 1"));
+        assert_eq!(buffer.len(), 2);
+
+        assert_eq!(buffer.flush(), vec!["accel 1", "depth 2"]);
+        assert!(buffer.is_empty());
+    }
+}
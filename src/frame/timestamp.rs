@@ -0,0 +1,183 @@
+//! Type for turning frame timestamps into a monotonic host-referenced timeline.
+//!
+//! Frame timestamps in the [`HardwareClock`](Rs2TimestampDomain::HardwareClock) domain come from a
+//! counter on the device. That counter wraps around, and restarts whenever the device is reset
+//! (e.g. after a USB reset), so consecutive timestamps can jump backwards or far ahead. The
+//! [`TimestampNormalizer`] anchors the timestamps of a stream to the host clock, and re-anchors
+//! them whenever such a discontinuity is detected, so that the resulting timeline never goes
+//! backwards.
+
+use super::prelude::FrameEx;
+use crate::kind::{Rs2FrameMetadata, Rs2TimestampDomain};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The default largest forward step between two timestamps that is not treated as a jump.
+const DEFAULT_MAX_STEP: Duration = Duration::from_secs(1);
+
+/// Maps the timestamps of a single stream onto a monotonic timeline in host milliseconds.
+///
+/// The first timestamp observed is anchored to the host time at which it was observed. Every
+/// later timestamp keeps its distance to the previous one, unless it went backwards (e.g. because
+/// the device clock wrapped around) or moved forward by more than the maximum step (e.g. because
+/// the device was reset). In that case the timeline is re-anchored, continuing from the previous
+/// timestamp by the host time that passed in between.
+///
+/// The offset between the raw and the normalized timestamps is exposed via
+/// [`TimestampNormalizer::offset`].
+#[derive(Debug, Clone)]
+pub struct TimestampNormalizer {
+    /// The timestamp domain of the frames that are normalized.
+    domain: Rs2TimestampDomain,
+    /// The largest forward step between two raw timestamps that is kept as-is, in milliseconds.
+    max_step_ms: f64,
+    /// The offset added to raw timestamps, in milliseconds, once the first one is observed.
+    offset_ms: Option<f64>,
+    /// The last observed raw timestamp, in milliseconds.
+    last_raw_ms: f64,
+    /// The host time at which the last timestamp was observed, in milliseconds.
+    last_host_ms: f64,
+    /// The last normalized timestamp, in milliseconds.
+    last_normalized_ms: f64,
+    /// The number of discontinuities detected so far.
+    discontinuities: usize,
+}
+
+impl TimestampNormalizer {
+    /// Construct a new normalizer for frames in the given timestamp domain.
+    ///
+    /// Forward steps of more than one second between consecutive timestamps are treated as
+    /// jumps. See [`TimestampNormalizer::with_max_step`] to change this.
+    pub fn new(domain: Rs2TimestampDomain) -> Self {
+        Self::with_max_step(domain, DEFAULT_MAX_STEP)
+    }
+
+    /// Construct a new normalizer which treats forward steps of more than `max_step` between
+    /// consecutive timestamps as jumps.
+    pub fn with_max_step(domain: Rs2TimestampDomain, max_step: Duration) -> Self {
+        Self {
+            domain,
+            max_step_ms: max_step.as_secs_f64() * 1000.0,
+            offset_ms: None,
+            last_raw_ms: 0.0,
+            last_host_ms: 0.0,
+            last_normalized_ms: 0.0,
+            discontinuities: 0,
+        }
+    }
+
+    /// Get the timestamp domain of the frames that are normalized.
+    pub fn domain(&self) -> Rs2TimestampDomain {
+        self.domain
+    }
+
+    /// Get the offset in milliseconds that is currently added to raw timestamps.
+    ///
+    /// Returns `None` if no timestamp has been observed yet.
+    pub fn offset(&self) -> Option<f64> {
+        self.offset_ms
+    }
+
+    /// Get the number of discontinuities (wrap-arounds or jumps) detected so far.
+    pub fn discontinuities(&self) -> usize {
+        self.discontinuities
+    }
+
+    /// Normalize a raw timestamp observed at the given host time, both in milliseconds.
+    ///
+    /// Returns the normalized timestamp in host milliseconds. The returned timestamps never
+    /// decrease.
+    pub fn normalize(&mut self, raw_ms: f64, host_ms: f64) -> f64 {
+        let offset_ms = match self.offset_ms {
+            None => host_ms - raw_ms,
+            Some(offset_ms) => {
+                let step_ms = raw_ms - self.last_raw_ms;
+                if (0.0..=self.max_step_ms).contains(&step_ms) {
+                    offset_ms
+                } else {
+                    self.discontinuities += 1;
+                    let elapsed_ms = (host_ms - self.last_host_ms).max(0.0);
+                    self.last_normalized_ms + elapsed_ms - raw_ms
+                }
+            }
+        };
+
+        let normalized_ms = match self.offset_ms {
+            Some(_) => (raw_ms + offset_ms).max(self.last_normalized_ms),
+            None => raw_ms + offset_ms,
+        };
+
+        self.offset_ms = Some(offset_ms);
+        self.last_raw_ms = raw_ms;
+        self.last_host_ms = host_ms;
+        self.last_normalized_ms = normalized_ms;
+        normalized_ms
+    }
+
+    /// Normalize the timestamp of a frame.
+    ///
+    /// The host time of the frame is taken from its
+    /// [time of arrival](Rs2FrameMetadata::TimeOfArrival) if available, and from the current
+    /// system time otherwise.
+    ///
+    /// Returns `None` if the frame is not in the timestamp domain of this normalizer. See
+    /// [`TimestampNormalizer::normalize`] for the returned value.
+    pub fn normalize_frame<F: FrameEx>(&mut self, frame: &F) -> Option<f64> {
+        if frame.timestamp_domain() != self.domain {
+            return None;
+        }
+
+        let host_ms = match frame.metadata(Rs2FrameMetadata::TimeOfArrival) {
+            Some(arrival) => arrival as f64,
+            None => {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs_f64()
+                    * 1000.0
+            }
+        };
+        Some(self.normalize(frame.timestamp(), host_ms))
+    }
+
+    /// Forget all previously observed timestamps, so that the next one is anchored anew.
+    pub fn reset(&mut self) {
+        *self = Self {
+            max_step_ms: self.max_step_ms,
+            ..Self::new(self.domain)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_timestamp_is_anchored_to_the_host() {
+        let mut normalizer = TimestampNormalizer::new(Rs2TimestampDomain::HardwareClock);
+        assert_eq!(normalizer.offset(), None);
+
+        assert_eq!(normalizer.normalize(100.0, 5000.0), 5000.0);
+        assert_eq!(normalizer.normalize(133.0, 5040.0), 5033.0);
+        assert_eq!(normalizer.offset(), Some(4900.0));
+        assert_eq!(normalizer.discontinuities(), 0);
+    }
+
+    #[test]
+    fn wrap_around_and_jumps_are_corrected() {
+        let mut normalizer = TimestampNormalizer::new(Rs2TimestampDomain::HardwareClock);
+
+        assert_eq!(normalizer.normalize(1000.0, 5000.0), 5000.0);
+        // The device clock wrapped around.
+        assert_eq!(normalizer.normalize(10.0, 5030.0), 5030.0);
+        assert_eq!(normalizer.normalize(40.0, 5061.0), 5060.0);
+        // The device clock jumped ahead, e.g. after a reset.
+        assert_eq!(normalizer.normalize(90_000.0, 5095.0), 5094.0);
+        assert_eq!(normalizer.discontinuities(), 2);
+        assert_eq!(normalizer.offset(), Some(5094.0 - 90_000.0));
+
+        normalizer.reset();
+        assert_eq!(normalizer.offset(), None);
+        assert_eq!(normalizer.discontinuities(), 0);
+    }
+}
@@ -63,11 +63,17 @@ pub mod extension;
 pub mod format;
 pub mod frame_metadata;
 pub mod hole_filling;
+pub mod log_severity;
+pub mod notification_category;
 pub mod option;
 pub mod persistence_control;
+pub mod playback_status;
+pub mod power_line_frequency;
 pub mod product_line;
+pub mod rs400_visual_preset;
 pub mod stream_kind;
 pub mod timestamp_domain;
+pub mod typed_option;
 
 pub use camera_info::Rs2CameraInfo;
 pub use color_scheme::ColorScheme;
@@ -80,8 +86,14 @@ pub use extension::{
 pub use format::Rs2Format;
 pub use frame_metadata::Rs2FrameMetadata;
 pub use hole_filling::HoleFillingMode;
-pub use option::{OptionSetError, Rs2Option, Rs2OptionRange};
+pub use log_severity::Rs2LogSeverity;
+pub use notification_category::Rs2NotificationCategory;
+pub use option::{OptionGetError, OptionSetError, Rs2Option, Rs2OptionRange};
 pub use persistence_control::PersistenceControl;
+pub use playback_status::Rs2PlaybackStatus;
+pub use power_line_frequency::Rs2PowerLineFrequency;
 pub use product_line::Rs2ProductLine;
+pub use rs400_visual_preset::Rs2Rs400VisualPreset;
 pub use stream_kind::Rs2StreamKind;
 pub use timestamp_domain::Rs2TimestampDomain;
+pub use typed_option::TypedOption;
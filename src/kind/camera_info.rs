@@ -1,5 +1,9 @@
 //! Enumeration of sensor and device information keys.
 
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use num_derive::{FromPrimitive, ToPrimitive};
 use realsense_sys as sys;
 
 /// A type describing the different keys used to access camera info from devices and sensors.
@@ -11,7 +15,7 @@ use realsense_sys as sys;
 /// types, or C-strings. We wrap these values in the `realsense-rust` API as `&CStr` types.
 ///
 #[repr(i32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Rs2CameraInfo {
     /// The name of the sensor or device.
     Name = sys::rs2_camera_info_RS2_CAMERA_INFO_NAME as i32,
@@ -47,6 +51,8 @@ pub enum Rs2CameraInfo {
     FirmwareUpdateId = sys::rs2_camera_info_RS2_CAMERA_INFO_FIRMWARE_UPDATE_ID as i32,
     /// IP address for remote camera.
     IpAddress = sys::rs2_camera_info_RS2_CAMERA_INFO_IP_ADDRESS as i32,
+    /// The path of the DFU (device firmware update) device node, for devices in recovery mode.
+    DfuDevicePath = sys::rs2_camera_info_RS2_CAMERA_INFO_DFU_DEVICE_PATH as i32,
     // Not included since this just tells us the total number of camera info options
     //
     // Count = sys::rs2_camera_info_RS2_CAMERA_INFO_COUNT,
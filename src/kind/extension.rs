@@ -292,6 +292,38 @@ pub const MISC_EXTENSIONS: [Rs2Extension; 15] = [
     Rs2Extension::Roi,
 ];
 
+impl Rs2Extension {
+    /// Predicate for whether the extension is one of the [`SENSOR_EXTENSIONS`].
+    pub fn is_sensor_extension(&self) -> bool {
+        SENSOR_EXTENSIONS.contains(self)
+    }
+
+    /// Predicate for whether the extension is one of the [`FRAME_EXTENSIONS`].
+    pub fn is_frame_extension(&self) -> bool {
+        FRAME_EXTENSIONS.contains(self)
+    }
+
+    /// Predicate for whether the extension is one of the [`FILTER_EXTENSIONS`].
+    pub fn is_filter_extension(&self) -> bool {
+        FILTER_EXTENSIONS.contains(self)
+    }
+
+    /// Predicate for whether the extension is one of the [`PROFILE_EXTENSIONS`].
+    pub fn is_profile_extension(&self) -> bool {
+        PROFILE_EXTENSIONS.contains(self)
+    }
+
+    /// Predicate for whether the extension is one of the [`DEVICE_EXTENSIONS`].
+    pub fn is_device_extension(&self) -> bool {
+        DEVICE_EXTENSIONS.contains(self)
+    }
+
+    /// Predicate for whether the extension is one of the [`MISC_EXTENSIONS`].
+    pub fn is_misc_extension(&self) -> bool {
+        MISC_EXTENSIONS.contains(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +338,16 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn extensions_belong_to_their_category() {
+        assert!(Rs2Extension::DepthSensor.is_sensor_extension());
+        assert!(!Rs2Extension::DepthSensor.is_frame_extension());
+        assert!(Rs2Extension::DepthFrame.is_frame_extension());
+        assert!(!Rs2Extension::DepthFrame.is_sensor_extension());
+        assert!(Rs2Extension::SpatialFilter.is_filter_extension());
+        assert!(Rs2Extension::VideoProfile.is_profile_extension());
+        assert!(Rs2Extension::UpdateDevice.is_device_extension());
+        assert!(Rs2Extension::AdvancedMode.is_misc_extension());
+    }
 }
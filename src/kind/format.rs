@@ -1,5 +1,6 @@
 //! Enumeration of frame data format & layout
 
+use super::Rs2StreamKind;
 use num_derive::{FromPrimitive, ToPrimitive};
 use realsense_sys as sys;
 
@@ -110,11 +111,217 @@ pub enum Rs2Format {
     Fg = sys::rs2_format_RS2_FORMAT_FG as i32,
     /// 12-bit per pixel
     Y411 = sys::rs2_format_RS2_FORMAT_Y411 as i32,
+    /// 16-bit per pixel interleaved.
+    ///
+    /// 16-bit left, 16-bit right.
+    Y16I = sys::rs2_format_RS2_FORMAT_Y16I as i32,
+    /// 12-bit per pixel YUV 4:2:0 image.
+    ///
+    /// Two lines of y values for every line of interleaved u, v values.
+    M420 = sys::rs2_format_RS2_FORMAT_M420 as i32,
+    /// Combined motion data, holding the orientation, angular velocity and linear acceleration
+    /// measured by the motion sensor at once.
+    CombinedMotion = sys::rs2_format_RS2_FORMAT_COMBINED_MOTION as i32,
     // Not included since this just tells us the total number of formats
     //
     // Count = sys::rs2_format_RS2_FORMAT_COUNT,
 }
 
+impl Rs2Format {
+    /// Get the number of bytes used by a single pixel of this format.
+    ///
+    /// This matches the per-pixel sizes librealsense2 uses for each format. Returns `None` for
+    /// formats that do not have a fixed, whole number of bytes per pixel, e.g. bit-packed formats
+    /// ([`Rs2Format::Raw10`], [`Rs2Format::W10`], [`Rs2Format::Y411`]), compressed formats
+    /// ([`Rs2Format::Mjpeg`], [`Rs2Format::Z16H`]), and formats that do not describe image data.
+    pub fn bytes_per_pixel(&self) -> Option<usize> {
+        match self {
+            Rs2Format::Y8 | Rs2Format::Raw8 | Rs2Format::Invi => Some(1),
+            Rs2Format::Z16
+            | Rs2Format::Disparity16
+            | Rs2Format::Yuyv
+            | Rs2Format::Uyvy
+            | Rs2Format::Y16
+            | Rs2Format::Raw16
+            | Rs2Format::Y10Bpack
+            | Rs2Format::Y8I
+            | Rs2Format::Fg => Some(2),
+            Rs2Format::Rgb8 | Rs2Format::Bgr8 | Rs2Format::Y12I => Some(3),
+            Rs2Format::Rgba8
+            | Rs2Format::Bgra8
+            | Rs2Format::Disparity32
+            | Rs2Format::Distance
+            | Rs2Format::Y16I => Some(4),
+            Rs2Format::Xyz32F | Rs2Format::MotionXyz32F => Some(12),
+            Rs2Format::Any
+            | Rs2Format::MotionRaw
+            | Rs2Format::GpioRaw
+            | Rs2Format::Mjpeg
+            | Rs2Format::Inzi
+            | Rs2Format::_6Dof
+            | Rs2Format::Raw10
+            | Rs2Format::W10
+            | Rs2Format::Z16H
+            | Rs2Format::Y411
+            | Rs2Format::M420
+            | Rs2Format::CombinedMotion => None,
+        }
+    }
+
+    /// Get the number of channels (values) stored per pixel of this format.
+    ///
+    /// YUYV / UYVY count as two channels, since every pixel holds its own luma value and one of
+    /// the two shared chroma values. Interleaved stereo formats ([`Rs2Format::Y8I`],
+    /// [`Rs2Format::Y12I`]) count as two channels, one for each imager.
+    ///
+    /// Returns `None` for formats without a well-defined number of channels per pixel, e.g.
+    /// compressed formats and formats that do not describe image data.
+    pub fn channels(&self) -> Option<usize> {
+        match self {
+            Rs2Format::Z16
+            | Rs2Format::Z16H
+            | Rs2Format::Disparity16
+            | Rs2Format::Disparity32
+            | Rs2Format::Distance
+            | Rs2Format::Y8
+            | Rs2Format::Y16
+            | Rs2Format::Y10Bpack
+            | Rs2Format::Raw8
+            | Rs2Format::Raw10
+            | Rs2Format::Raw16
+            | Rs2Format::W10
+            | Rs2Format::Invi
+            | Rs2Format::Fg => Some(1),
+            Rs2Format::Yuyv
+            | Rs2Format::Uyvy
+            | Rs2Format::Y8I
+            | Rs2Format::Y12I
+            | Rs2Format::Y16I
+            | Rs2Format::Inzi => Some(2),
+            Rs2Format::Rgb8 | Rs2Format::Bgr8 | Rs2Format::Xyz32F | Rs2Format::MotionXyz32F => {
+                Some(3)
+            }
+            Rs2Format::Rgba8 | Rs2Format::Bgra8 => Some(4),
+            Rs2Format::Any
+            | Rs2Format::MotionRaw
+            | Rs2Format::GpioRaw
+            | Rs2Format::Mjpeg
+            | Rs2Format::_6Dof
+            | Rs2Format::Y411
+            | Rs2Format::M420
+            | Rs2Format::CombinedMotion => None,
+        }
+    }
+
+    /// Predicate for determining if the format holds depth data.
+    ///
+    /// Returns true for depth ([`Rs2Format::Z16`], [`Rs2Format::Z16H`]), distance
+    /// ([`Rs2Format::Distance`]) and disparity ([`Rs2Format::Disparity16`],
+    /// [`Rs2Format::Disparity32`]) formats.
+    pub fn is_depth(&self) -> bool {
+        matches!(
+            self,
+            Rs2Format::Z16
+                | Rs2Format::Z16H
+                | Rs2Format::Distance
+                | Rs2Format::Disparity16
+                | Rs2Format::Disparity32
+        )
+    }
+
+    /// Predicate for determining if the format holds motion (IMU) data.
+    ///
+    /// Returns true for [`Rs2Format::MotionRaw`], [`Rs2Format::MotionXyz32F`] and
+    /// [`Rs2Format::CombinedMotion`]. Frames in any other format do not hold motion data, and must
+    /// not be interpreted as such.
+    pub fn is_motion(&self) -> bool {
+        matches!(
+            self,
+            Rs2Format::MotionRaw | Rs2Format::MotionXyz32F | Rs2Format::CombinedMotion
+        )
+    }
+
+    /// Predicate for whether streams of the given kind can be produced in this format.
+    ///
+    /// This is a coarse table of the formats that librealsense2 devices offer for each stream
+    /// kind, meant to catch obviously wrong combinations (e.g. [`Rs2Format::Z16`] on a
+    /// [`Rs2StreamKind::Color`] stream) early. A combination for which this returns `true` may
+    /// still not be supported by a particular device. [`Rs2Format::Any`] and
+    /// [`Rs2StreamKind::Any`] are valid with everything.
+    pub fn is_valid_for(&self, kind: Rs2StreamKind) -> bool {
+        if *self == Rs2Format::Any {
+            return true;
+        }
+
+        match kind {
+            Rs2StreamKind::Any => true,
+            Rs2StreamKind::Depth => matches!(
+                self,
+                Rs2Format::Z16
+                    | Rs2Format::Z16H
+                    | Rs2Format::Disparity16
+                    | Rs2Format::Disparity32
+                    | Rs2Format::Distance
+                    | Rs2Format::Inzi
+                    | Rs2Format::Fg
+            ),
+            Rs2StreamKind::Color => matches!(
+                self,
+                Rs2Format::Yuyv
+                    | Rs2Format::Uyvy
+                    | Rs2Format::Rgb8
+                    | Rs2Format::Bgr8
+                    | Rs2Format::Rgba8
+                    | Rs2Format::Bgra8
+                    | Rs2Format::Mjpeg
+                    | Rs2Format::Y8
+                    | Rs2Format::Y16
+                    | Rs2Format::Y411
+                    | Rs2Format::M420
+                    | Rs2Format::Raw8
+                    | Rs2Format::Raw10
+                    | Rs2Format::Raw16
+            ),
+            // Some devices produce their infrared streams with the color imager, and thus offer
+            // them in color formats as well.
+            Rs2StreamKind::Infrared => matches!(
+                self,
+                Rs2Format::Y8
+                    | Rs2Format::Y8I
+                    | Rs2Format::Y10Bpack
+                    | Rs2Format::Y12I
+                    | Rs2Format::Y16
+                    | Rs2Format::Y16I
+                    | Rs2Format::Invi
+                    | Rs2Format::Inzi
+                    | Rs2Format::W10
+                    | Rs2Format::Fg
+                    | Rs2Format::Yuyv
+                    | Rs2Format::Uyvy
+                    | Rs2Format::Rgb8
+                    | Rs2Format::Bgr8
+                    | Rs2Format::Rgba8
+                    | Rs2Format::Bgra8
+                    | Rs2Format::Raw8
+                    | Rs2Format::Raw10
+                    | Rs2Format::Raw16
+            ),
+            Rs2StreamKind::Fisheye => matches!(
+                self,
+                Rs2Format::Y8
+                    | Rs2Format::Y16
+                    | Rs2Format::Raw8
+                    | Rs2Format::Raw10
+                    | Rs2Format::Raw16
+            ),
+            Rs2StreamKind::Gyro | Rs2StreamKind::Accel => self.is_motion(),
+            Rs2StreamKind::Gpio => *self == Rs2Format::GpioRaw,
+            Rs2StreamKind::Pose => *self == Rs2Format::_6Dof,
+            Rs2StreamKind::Confidence => *self == Rs2Format::Raw8,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +336,43 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn pixel_layouts_are_consistent() {
+        assert_eq!(Rs2Format::Z16.bytes_per_pixel(), Some(2));
+        assert_eq!(Rs2Format::Rgb8.bytes_per_pixel(), Some(3));
+        assert_eq!(Rs2Format::Bgra8.channels(), Some(4));
+        assert_eq!(Rs2Format::Raw10.bytes_per_pixel(), None);
+        assert!(Rs2Format::Z16.is_depth());
+        assert!(!Rs2Format::Y16.is_depth());
+        assert!(Rs2Format::MotionXyz32F.is_motion());
+        assert!(Rs2Format::CombinedMotion.is_motion());
+        assert!(!Rs2Format::Xyz32F.is_motion());
+
+        // Every format with 8-bit channels uses exactly one byte per channel.
+        for format in [
+            Rs2Format::Y8,
+            Rs2Format::Y8I,
+            Rs2Format::Rgb8,
+            Rs2Format::Bgr8,
+            Rs2Format::Rgba8,
+            Rs2Format::Bgra8,
+        ] {
+            assert_eq!(format.bytes_per_pixel(), format.channels());
+        }
+    }
+
+    #[test]
+    fn formats_are_checked_against_stream_kinds() {
+        assert!(Rs2Format::Z16.is_valid_for(Rs2StreamKind::Depth));
+        assert!(Rs2Format::Rgb8.is_valid_for(Rs2StreamKind::Color));
+        assert!(Rs2Format::Y8.is_valid_for(Rs2StreamKind::Infrared));
+        assert!(Rs2Format::MotionXyz32F.is_valid_for(Rs2StreamKind::Gyro));
+        assert!(Rs2Format::Any.is_valid_for(Rs2StreamKind::Pose));
+        assert!(Rs2Format::Z16.is_valid_for(Rs2StreamKind::Any));
+
+        assert!(!Rs2Format::Z16.is_valid_for(Rs2StreamKind::Color));
+        assert!(!Rs2Format::MotionXyz32F.is_valid_for(Rs2StreamKind::Depth));
+        assert!(!Rs2Format::Rgb8.is_valid_for(Rs2StreamKind::Accel));
+    }
 }
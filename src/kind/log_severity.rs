@@ -0,0 +1,72 @@
+//! Enumeration describing the severity of log messages and notifications.
+
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
+/// Enumeration of possible severities of librealsense2 log messages and notifications.
+///
+/// Severities are ordered, so that when used as a logging threshold, every message with a
+/// severity greater than or equal to the threshold is reported.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rs2LogSeverity {
+    /// Detailed information about ordinary operations
+    Debug = sys::rs2_log_severity_RS2_LOG_SEVERITY_DEBUG as i32,
+    /// Terse information about ordinary operations
+    Info = sys::rs2_log_severity_RS2_LOG_SEVERITY_INFO as i32,
+    /// Indication of possible failure
+    Warn = sys::rs2_log_severity_RS2_LOG_SEVERITY_WARN as i32,
+    /// Indication of definite failure
+    Error = sys::rs2_log_severity_RS2_LOG_SEVERITY_ERROR as i32,
+    /// Indication of unrecoverable failure
+    Fatal = sys::rs2_log_severity_RS2_LOG_SEVERITY_FATAL as i32,
+    /// No logging will occur
+    None = sys::rs2_log_severity_RS2_LOG_SEVERITY_NONE as i32,
+    /* Not included since this just tells us the total number of severities
+     *
+     * Count = sys::rs2_log_severity_RS2_LOG_SEVERITY_COUNT, */
+    /* Not included since this is an alias of `Debug`
+     *
+     * All = sys::rs2_log_severity_RS2_LOG_SEVERITY_ALL, */
+}
+
+impl Rs2LogSeverity {
+    /// Get the log severity variant as a `&CStr`
+    pub fn as_cstr(&self) -> &'static CStr {
+        unsafe {
+            let ptr = sys::rs2_log_severity_to_string(*self as sys::rs2_log_severity);
+            CStr::from_ptr(ptr)
+        }
+    }
+
+    /// Get the log severity variant as a `&str`
+    pub fn as_str(&self) -> &'static str {
+        self.as_cstr().to_str().unwrap()
+    }
+}
+
+impl ToString for Rs2LogSeverity {
+    fn to_string(&self) -> String {
+        self.as_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_log_severity_RS2_LOG_SEVERITY_COUNT as i32 {
+            assert!(
+                Rs2LogSeverity::from_i32(i).is_some(),
+                "Rs2LogSeverity variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
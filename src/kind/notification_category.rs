@@ -0,0 +1,72 @@
+//! Enumeration describing the categories of notifications raised by a sensor.
+
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
+/// Enumeration of possible categories of sensor notifications.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2NotificationCategory {
+    /// Frames did not arrive within 5 seconds
+    FramesTimeout = sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_FRAMES_TIMEOUT as i32,
+    /// Received a partial / incomplete frame
+    FrameCorrupted =
+        sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_FRAME_CORRUPTED as i32,
+    /// Error reported from the device
+    HardwareError = sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_HARDWARE_ERROR as i32,
+    /// General hardware notification that is not an error
+    HardwareEvent = sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_HARDWARE_EVENT as i32,
+    /// Received an unknown error from the device
+    UnknownError = sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_UNKNOWN_ERROR as i32,
+    /// Current firmware version installed is not the latest available
+    FirmwareUpdateRecommended =
+        sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_FIRMWARE_UPDATE_RECOMMENDED as i32,
+    /// A relocalization event has updated the pose provided by a pose sensor
+    PoseRelocalization =
+        sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_POSE_RELOCALIZATION as i32,
+    /* Not included since this just tells us the total number of categories
+     *
+     * Count = sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_COUNT, */
+}
+
+impl Rs2NotificationCategory {
+    /// Get the notification category variant as a `&CStr`
+    pub fn as_cstr(&self) -> &'static CStr {
+        unsafe {
+            let ptr =
+                sys::rs2_notification_category_to_string(*self as sys::rs2_notification_category);
+            CStr::from_ptr(ptr)
+        }
+    }
+
+    /// Get the notification category variant as a `&str`
+    pub fn as_str(&self) -> &'static str {
+        self.as_cstr().to_str().unwrap()
+    }
+}
+
+impl ToString for Rs2NotificationCategory {
+    fn to_string(&self) -> String {
+        self.as_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_notification_category_RS2_NOTIFICATION_CATEGORY_COUNT as i32 {
+            assert!(
+                Rs2NotificationCategory::from_i32(i).is_some(),
+                "Rs2NotificationCategory variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
@@ -9,7 +9,7 @@
 //! is supported by the sensor before attempting to set it. Failure to do so may cause
 //! an error in operation.
 
-use super::Rs2Exception;
+use super::{Rs2Exception, Rs2Extension};
 use num_derive::{FromPrimitive, ToPrimitive};
 use realsense_sys as sys;
 use std::ffi::CStr;
@@ -29,6 +29,14 @@ pub enum OptionSetError {
     CouldNotSetOption(Rs2Exception, String),
 }
 
+/// Occur when an option cannot be read.
+#[derive(Error, Debug)]
+pub enum OptionGetError {
+    /// The requested option could not be read. Reason is reported by the sensor.
+    #[error("Could not get option. Type: {0}; Reason: {1}")]
+    CouldNotGetOption(Rs2Exception, String),
+}
+
 /// The enumeration of options available in the RealSense SDK.
 ///
 /// The majority of the options presented have a specific range of valid values. Run
@@ -312,12 +320,141 @@ impl Rs2Option {
     }
 }
 
+/// Sensor extensions of options found on color sensors only.
+const COLOR: &[Rs2Extension] = &[Rs2Extension::ColorSensor];
+/// Sensor extensions of options found on depth sensors only.
+const DEPTH: &[Rs2Extension] = &[Rs2Extension::DepthSensor];
+/// Sensor extensions of options found on motion sensors only.
+const MOTION: &[Rs2Extension] = &[Rs2Extension::MotionSensor];
+/// Sensor extensions of options found on pose sensors only.
+const POSE: &[Rs2Extension] = &[Rs2Extension::PoseSensor];
+/// Sensor extensions of options controlling the exposure of a camera.
+const IMAGING: &[Rs2Extension] = &[
+    Rs2Extension::DepthSensor,
+    Rs2Extension::ColorSensor,
+    Rs2Extension::FishEyeSensor,
+];
+/// Sensor extensions of options found on every streaming sensor.
+const STREAMING: &[Rs2Extension] = &[
+    Rs2Extension::DepthSensor,
+    Rs2Extension::ColorSensor,
+    Rs2Extension::MotionSensor,
+];
+
+impl Rs2Option {
+    /// Get the sensor extensions on which this option is typically found.
+    ///
+    /// This is a static table following Intel's documentation of the options, which can be
+    /// compared against [`Sensor::extension`](crate::sensor::Sensor::extension) to pre-filter the
+    /// options shown for a sensor. It does not guarantee that a sensor supports the option, so
+    /// support still has to be queried before reading or writing it.
+    ///
+    /// Returns an empty slice for options of processing blocks (e.g. [`Rs2Option::HolesFill`]),
+    /// which are not found on sensors.
+    pub fn typical_sensors(&self) -> &'static [Rs2Extension] {
+        match self {
+            Rs2Option::BacklightCompensation
+            | Rs2Option::Brightness
+            | Rs2Option::Contrast
+            | Rs2Option::Gamma
+            | Rs2Option::Hue
+            | Rs2Option::Saturation
+            | Rs2Option::Sharpness
+            | Rs2Option::WhiteBalance
+            | Rs2Option::EnableAutoWhiteBalance
+            | Rs2Option::PowerLineFrequency
+            | Rs2Option::AutoExposurePriority => COLOR,
+            Rs2Option::Exposure
+            | Rs2Option::Gain
+            | Rs2Option::EnableAutoExposure
+            | Rs2Option::AutoExposureConvergeStep => IMAGING,
+            Rs2Option::FramesQueueSize | Rs2Option::GlobalTimeEnabled => STREAMING,
+            Rs2Option::MotionModuleTemperature | Rs2Option::EnableMotionCorrection => MOTION,
+            Rs2Option::EnableMapping
+            | Rs2Option::EnableRelocalization
+            | Rs2Option::EnablePoseJumping
+            | Rs2Option::EnableDynamicCalibration
+            | Rs2Option::EnableMapPreservation => POSE,
+            Rs2Option::VisualPreset
+            | Rs2Option::LaserPower
+            | Rs2Option::Accuracy
+            | Rs2Option::MotionRange
+            | Rs2Option::FilterOption
+            | Rs2Option::ConfidenceThreshold
+            | Rs2Option::EmitterEnabled
+            | Rs2Option::AutoExposureMode
+            | Rs2Option::AsicTemperature
+            | Rs2Option::ErrorPollingEnabled
+            | Rs2Option::ProjectorTemperature
+            | Rs2Option::OutputTriggerEnabled
+            | Rs2Option::DepthUnits
+            | Rs2Option::StereoBaseline
+            | Rs2Option::InterCamSyncMode
+            | Rs2Option::EmitterOnOff
+            | Rs2Option::LldTemperature
+            | Rs2Option::McTemperature
+            | Rs2Option::MaTemperature
+            | Rs2Option::HardwarePreset
+            | Rs2Option::ApdTemperature
+            | Rs2Option::DepthOffset
+            | Rs2Option::LedPower
+            | Rs2Option::FreefallDetectionEnabled
+            | Rs2Option::AvalanchePhotoDiode
+            | Rs2Option::PostProcessingSharpening
+            | Rs2Option::PreProcessingSharpening
+            | Rs2Option::NoiseFiltering
+            | Rs2Option::InvalidationBypass
+            | Rs2Option::DigitalGain
+            | Rs2Option::SensoeMode
+            | Rs2Option::EmitterAlwaysOn
+            | Rs2Option::ThermalCompensation
+            | Rs2Option::HostPerformance
+            | Rs2Option::HdrEnabled
+            | Rs2Option::SequenceName
+            | Rs2Option::SequenceSize
+            | Rs2Option::SequenceId
+            | Rs2Option::HumidityTemperature
+            | Rs2Option::EnableMaxUsableRange
+            | Rs2Option::AlternateIr
+            | Rs2Option::NoiseEstimation
+            | Rs2Option::EnableIrReflectivity
+            | Rs2Option::AutoExposureLimit
+            | Rs2Option::AutoGainLimit
+            | Rs2Option::AutoReceiverSensitivity
+            | Rs2Option::TransmitterFrequency
+            | Rs2Option::VerticalBinning
+            | Rs2Option::ReceiverSensitivity
+            | Rs2Option::AutoExposureLimitToggle
+            | Rs2Option::AutoGainLimitToggle
+            | Rs2Option::EmitterFrequency
+            | Rs2Option::DepthAutoExposureMode => DEPTH,
+            Rs2Option::TotalFrameDrops
+            | Rs2Option::ColorScheme
+            | Rs2Option::HistogramEqualizationEnabled
+            | Rs2Option::MinDistance
+            | Rs2Option::MaxDistance
+            | Rs2Option::TextureSource
+            | Rs2Option::FilterMagnitude
+            | Rs2Option::FilterSmoothAlpha
+            | Rs2Option::FilterSmoothDelta
+            | Rs2Option::HolesFill
+            | Rs2Option::StreamFilter
+            | Rs2Option::StreamFormatFilter
+            | Rs2Option::StreamIndexFilter => &[],
+        }
+    }
+}
+
 impl ToString for Rs2Option {
     fn to_string(&self) -> String {
         self.to_str().to_owned()
     }
 }
 
+/// The number of steps above which an option range is considered continuous, see
+/// [`Rs2OptionRange::is_continuous`].
+const CONTINUOUS_RANGE_MIN_STEPS: f32 = 10.0;
+
 /// The range of available values of a supported option.
 pub struct Rs2OptionRange {
     /// The minimum value which will be accepted for this option
@@ -331,10 +468,109 @@ pub struct Rs2OptionRange {
     pub default: f32,
 }
 
+impl Rs2OptionRange {
+    /// Snap `value` to the nearest value accepted for the option.
+    ///
+    /// The value is clamped to the range, and for options which accept discrete values, rounded to
+    /// the nearest multiple of the step counted from the minimum.
+    pub fn snap(&self, value: f32) -> f32 {
+        let clamped = value.max(self.min).min(self.max);
+        if self.step <= 0.0 {
+            return clamped;
+        }
+
+        let snapped = self.min + ((clamped - self.min) / self.step).round() * self.step;
+        if snapped > self.max {
+            snapped - self.step
+        } else {
+            snapped
+        }
+    }
+
+    /// Predicate for whether the option is an on/off switch, i.e. only accepts the values zero and
+    /// one.
+    pub fn is_boolean(&self) -> bool {
+        self.min == 0.0 && self.max == 1.0 && self.step == 1.0
+    }
+
+    /// Predicate for whether the option accepts (practically) any value between its minimum and
+    /// maximum, e.g. to present it as a slider.
+    ///
+    /// librealsense2 reports a step for almost every option, so options are considered continuous
+    /// if their range holds more than ten steps (e.g. exposure, from 1
+    /// to 165000 in steps of 1), or if they have no step at all. Options with fewer steps (e.g.
+    /// visual presets) are better presented as a list of values, and options which accept only a
+    /// single value, as do some options of motion sensors, are neither boolean nor continuous.
+    pub fn is_continuous(&self) -> bool {
+        if self.max <= self.min {
+            return false;
+        }
+
+        self.step <= 0.0 || (self.max - self.min) / self.step > CONTINUOUS_RANGE_MIN_STEPS
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn options_are_grouped_by_sensor() {
+        assert_eq!(
+            Rs2Option::Hue.typical_sensors(),
+            &[Rs2Extension::ColorSensor]
+        );
+        assert_eq!(
+            Rs2Option::LaserPower.typical_sensors(),
+            &[Rs2Extension::DepthSensor]
+        );
+        assert!(Rs2Option::Exposure
+            .typical_sensors()
+            .contains(&Rs2Extension::ColorSensor));
+        assert!(Rs2Option::HolesFill.typical_sensors().is_empty());
+    }
+
+    #[test]
+    fn values_snap_to_the_nearest_step() {
+        let range = Rs2OptionRange {
+            min: 1.0,
+            max: 10.0,
+            step: 2.0,
+            default: 1.0,
+        };
+        assert_eq!(range.snap(0.0), 1.0);
+        assert_eq!(range.snap(3.9), 3.0);
+        assert_eq!(range.snap(4.1), 5.0);
+        assert_eq!(range.snap(12.0), 9.0);
+
+        let continuous = Rs2OptionRange { step: 0.0, ..range };
+        assert_eq!(continuous.snap(4.1), 4.1);
+    }
+
+    #[test]
+    fn ranges_are_classified_by_their_values() {
+        let range = |min, max, step| Rs2OptionRange {
+            min,
+            max,
+            step,
+            default: min,
+        };
+
+        // Auto-exposure toggle.
+        assert!(range(0.0, 1.0, 1.0).is_boolean());
+        assert!(!range(0.0, 1.0, 1.0).is_continuous());
+        // Exposure, laser power and depth units.
+        assert!(range(1.0, 165000.0, 1.0).is_continuous());
+        assert!(range(0.0, 360.0, 30.0).is_continuous());
+        assert!(range(0.000001, 0.01, 0.000001).is_continuous());
+        assert!(range(1.0, 10000.0, 0.0).is_continuous());
+        // Visual presets.
+        assert!(!range(0.0, 5.0, 1.0).is_boolean());
+        assert!(!range(0.0, 5.0, 1.0).is_continuous());
+        assert!(!range(1.0, 1.0, 0.0).is_boolean());
+        assert!(!range(1.0, 1.0, 0.0).is_continuous());
+    }
+
     #[test]
     fn all_variants_exist() {
         let deprecated_options = [
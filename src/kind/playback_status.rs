@@ -0,0 +1,64 @@
+//! Enumeration describing the status of a playback device.
+
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
+/// Enumeration of possible states of a playback device.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2PlaybackStatus {
+    /// Unknown state
+    Unknown = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_UNKNOWN as i32,
+    /// One or more sensors were started, playback is reading and raising data
+    Playing = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_PLAYING as i32,
+    /// One or more sensors were started, but playback paused reading and paused raising data
+    Paused = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_PAUSED as i32,
+    /// All sensors were stopped, or playback has ended (all data was read).
+    ///
+    /// This is the initial playback status.
+    Stopped = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_STOPPED as i32,
+    /* Not included since this just tells us the total number of statuses
+     *
+     * Count = sys::rs2_playback_status_RS2_PLAYBACK_STATUS_COUNT, */
+}
+
+impl Rs2PlaybackStatus {
+    /// Get the playback status variant as a `&CStr`
+    pub fn as_cstr(&self) -> &'static CStr {
+        unsafe {
+            let ptr = sys::rs2_playback_status_to_string(*self as sys::rs2_playback_status);
+            CStr::from_ptr(ptr)
+        }
+    }
+
+    /// Get the playback status variant as a `&str`
+    pub fn as_str(&self) -> &'static str {
+        self.as_cstr().to_str().unwrap()
+    }
+}
+
+impl ToString for Rs2PlaybackStatus {
+    fn to_string(&self) -> String {
+        self.as_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_playback_status_RS2_PLAYBACK_STATUS_COUNT as i32 {
+            assert!(
+                Rs2PlaybackStatus::from_i32(i).is_some(),
+                "Rs2PlaybackStatus variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Enumeration of power line frequencies that color sensors can compensate for.
+//!
+//! Artificial lighting flickers at twice the frequency of the power line it is connected to. Color
+//! sensors can adjust their exposure to avoid this flicker showing up as banding in the image.
+
+/// A type describing the power line frequency that the sensor compensates for.
+///
+/// These are the values of the
+/// [`PowerLineFrequency`](crate::kind::Rs2Option::PowerLineFrequency) option.
+#[repr(usize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2PowerLineFrequency {
+    /// Do not compensate for power line flicker.
+    Disabled = 0,
+    /// Compensate for 50Hz power lines.
+    FiftyHertz = 1,
+    /// Compensate for 60Hz power lines.
+    SixtyHertz = 2,
+    /// Detect the power line frequency automatically.
+    Auto = 3,
+}
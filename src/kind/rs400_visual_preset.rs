@@ -0,0 +1,73 @@
+//! Enumeration of visual presets of D400 series depth sensors.
+
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use realsense_sys as sys;
+use std::ffi::CStr;
+
+/// Enumeration of the visual presets of D400 series depth sensors.
+///
+/// Each preset is a set of depth settings optimized for a specific type of usage. These are the
+/// values of the [`VisualPreset`](crate::kind::Rs2Option::VisualPreset) option of D400 series
+/// depth sensors.
+#[repr(i32)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rs2Rs400VisualPreset {
+    /// The depth settings were changed individually, and do not match any preset.
+    Custom = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_CUSTOM as i32,
+    /// General purpose settings, balancing accuracy and density.
+    Default = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_DEFAULT as i32,
+    /// Settings optimized for hand tracking and gesture recognition.
+    Hand = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HAND as i32,
+    /// High confidence threshold, for few but accurate depth values.
+    HighAccuracy = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HIGH_ACCURACY as i32,
+    /// Low confidence threshold, for many depth values at the cost of accuracy.
+    HighDensity = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_HIGH_DENSITY as i32,
+    /// Settings in between [`HighAccuracy`](Self::HighAccuracy) and
+    /// [`HighDensity`](Self::HighDensity).
+    MediumDensity = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_MEDIUM_DENSITY as i32,
+    /// Settings which remove the projected infrared pattern from the infrared streams.
+    RemoveIrPattern = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_REMOVE_IR_PATTERN as i32,
+    /* Not included since this just tells us the total number of presets
+     *
+     * Count = sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_COUNT, */
+}
+
+impl Rs2Rs400VisualPreset {
+    /// Get the visual preset variant as a `&CStr`
+    pub fn as_cstr(&self) -> &'static CStr {
+        unsafe {
+            let ptr = sys::rs2_rs400_visual_preset_to_string(*self as sys::rs2_rs400_visual_preset);
+            CStr::from_ptr(ptr)
+        }
+    }
+
+    /// Get the visual preset variant as a `&str`
+    pub fn as_str(&self) -> &'static str {
+        self.as_cstr().to_str().unwrap()
+    }
+}
+
+impl ToString for Rs2Rs400VisualPreset {
+    fn to_string(&self) -> String {
+        self.as_str().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_variants_exist() {
+        for i in 0..sys::rs2_rs400_visual_preset_RS2_RS400_VISUAL_PRESET_COUNT as i32 {
+            assert!(
+                Rs2Rs400VisualPreset::from_i32(i).is_some(),
+                "Rs2Rs400VisualPreset variant for ordinal {} does not exist.",
+                i,
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Streams are different types of data provided by RealSense devices.
 
+use super::Rs2Format;
 use num_derive::{FromPrimitive, ToPrimitive};
 use realsense_sys as sys;
 use serde::{Deserialize, Serialize};
@@ -44,6 +45,28 @@ pub enum Rs2StreamKind {
      * Count = sys::rs2_stream_RS2_STREAM_COUNT, */
 }
 
+impl Rs2StreamKind {
+    /// Get the format most commonly used for streams of this kind.
+    ///
+    /// E.g. [`Rs2Format::Z16`] for depth, [`Rs2Format::Rgb8`] for color and [`Rs2Format::Y8`] for
+    /// infrared streams. The format is always valid for this stream kind (see
+    /// [`Rs2Format::is_valid_for`]). Returns [`Rs2Format::Any`] for [`Rs2StreamKind::Any`].
+    pub fn default_format(&self) -> Rs2Format {
+        match self {
+            Rs2StreamKind::Any => Rs2Format::Any,
+            Rs2StreamKind::Depth => Rs2Format::Z16,
+            Rs2StreamKind::Color => Rs2Format::Rgb8,
+            Rs2StreamKind::Infrared => Rs2Format::Y8,
+            Rs2StreamKind::Fisheye => Rs2Format::Y8,
+            Rs2StreamKind::Gyro => Rs2Format::MotionXyz32F,
+            Rs2StreamKind::Accel => Rs2Format::MotionXyz32F,
+            Rs2StreamKind::Gpio => Rs2Format::GpioRaw,
+            Rs2StreamKind::Pose => Rs2Format::_6Dof,
+            Rs2StreamKind::Confidence => Rs2Format::Raw8,
+        }
+    }
+}
+
 impl std::fmt::Display for Rs2StreamKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let string = match self {
@@ -66,6 +89,19 @@ impl std::fmt::Display for Rs2StreamKind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn default_formats_are_valid() {
+        let kinds =
+            (0..sys::rs2_stream_RS2_STREAM_COUNT as i32).filter_map(Rs2StreamKind::from_i32);
+        for kind in kinds {
+            assert!(
+                kind.default_format().is_valid_for(kind),
+                "Default format of {} is not valid for it.",
+                kind,
+            );
+        }
+    }
+
     #[test]
     fn all_variants_exist() {
         for i in 0..sys::rs2_stream_RS2_STREAM_COUNT as i32 {
@@ -0,0 +1,145 @@
+//! Options with typed values.
+//!
+//! Option values are passed to and from librealsense2 as `f32`s, even for options that are really
+//! booleans or enumerations. The [`TypedOption`] trait describes how the values of such an option
+//! are encoded, so that they can be read and written with their proper type via
+//! [`Sensor::get_typed`](crate::sensor::Sensor::get_typed) and
+//! [`Sensor::set_typed`](crate::sensor::Sensor::set_typed):
+//!
+//! ```no_run
+//! use realsense_rust::kind::typed_option::EmitterEnabled;
+//! # fn f(sensor: &mut realsense_rust::sensor::Sensor) -> anyhow::Result<()> {
+//!
+//! sensor.set_typed::<EmitterEnabled>(true)?;
+//! assert_eq!(sensor.get_typed::<EmitterEnabled>(), Some(true));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Rs2Option, Rs2PowerLineFrequency, Rs2Rs400VisualPreset};
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+/// An option whose values are of type [`TypedOption::Value`].
+pub trait TypedOption {
+    /// The type of the values of the option.
+    type Value;
+
+    /// The option being described.
+    fn option() -> Rs2Option;
+
+    /// Encode a value as the `f32` passed to librealsense2.
+    fn encode(value: Self::Value) -> f32;
+
+    /// Decode a value from the `f32` returned by librealsense2.
+    ///
+    /// Returns `None` if the value does not correspond to any value of the type.
+    fn decode(value: f32) -> Option<Self::Value>;
+}
+
+/// Whether the emitter (laser projector) is turned on, see [`Rs2Option::EmitterEnabled`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmitterEnabled;
+
+impl TypedOption for EmitterEnabled {
+    type Value = bool;
+
+    fn option() -> Rs2Option {
+        Rs2Option::EmitterEnabled
+    }
+
+    fn encode(value: bool) -> f32 {
+        if value {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn decode(value: f32) -> Option<bool> {
+        Some(value != 0.0)
+    }
+}
+
+/// The power line frequency compensated for, see [`Rs2Option::PowerLineFrequency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PowerLineFrequency;
+
+impl TypedOption for PowerLineFrequency {
+    type Value = Rs2PowerLineFrequency;
+
+    fn option() -> Rs2Option {
+        Rs2Option::PowerLineFrequency
+    }
+
+    fn encode(value: Rs2PowerLineFrequency) -> f32 {
+        value as usize as f32
+    }
+
+    fn decode(value: f32) -> Option<Rs2PowerLineFrequency> {
+        match value as usize {
+            0 => Some(Rs2PowerLineFrequency::Disabled),
+            1 => Some(Rs2PowerLineFrequency::FiftyHertz),
+            2 => Some(Rs2PowerLineFrequency::SixtyHertz),
+            3 => Some(Rs2PowerLineFrequency::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// The visual preset of a D400 series depth sensor, see [`Rs2Option::VisualPreset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VisualPreset;
+
+impl TypedOption for VisualPreset {
+    type Value = Rs2Rs400VisualPreset;
+
+    fn option() -> Rs2Option {
+        Rs2Option::VisualPreset
+    }
+
+    fn encode(value: Rs2Rs400VisualPreset) -> f32 {
+        value as i32 as f32
+    }
+
+    fn decode(value: f32) -> Option<Rs2Rs400VisualPreset> {
+        Rs2Rs400VisualPreset::from_i32(value as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_survive_encoding() {
+        for enabled in [false, true] {
+            assert_eq!(
+                EmitterEnabled::decode(EmitterEnabled::encode(enabled)),
+                Some(enabled)
+            );
+        }
+
+        for frequency in [
+            Rs2PowerLineFrequency::Disabled,
+            Rs2PowerLineFrequency::FiftyHertz,
+            Rs2PowerLineFrequency::SixtyHertz,
+            Rs2PowerLineFrequency::Auto,
+        ] {
+            assert_eq!(
+                PowerLineFrequency::decode(PowerLineFrequency::encode(frequency)),
+                Some(frequency)
+            );
+        }
+        assert_eq!(PowerLineFrequency::decode(7.0), None);
+
+        assert_eq!(
+            VisualPreset::encode(Rs2Rs400VisualPreset::HighAccuracy),
+            3.0
+        );
+        assert_eq!(
+            VisualPreset::decode(4.0),
+            Some(Rs2Rs400VisualPreset::HighDensity)
+        );
+    }
+}
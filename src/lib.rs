@@ -9,9 +9,12 @@ pub mod docs;
 mod error;
 pub mod frame;
 pub mod kind;
+pub mod log;
+pub mod notification;
 pub mod pipeline;
 pub mod sensor;
 pub mod stream_profile;
+pub mod sync;
 
 // pub mod frame_queue;
 // pub mod processing_block;
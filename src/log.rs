@@ -0,0 +1,111 @@
+//! Functions for configuring the internal logger of librealsense2.
+//!
+//! librealsense2 has its own global logger, which reports internal diagnostics (e.g. USB issues,
+//! firmware errors). By default these messages are not reported anywhere. The functions in this
+//! module route them either to the console or to a callback, e.g. to forward them into the `log`
+//! or `tracing` ecosystems.
+
+use crate::{
+    check_rs2_error,
+    kind::{Rs2Exception, Rs2LogSeverity},
+};
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use realsense_sys as sys;
+use std::{
+    ffi::CStr,
+    os::raw::c_void,
+    panic::{self, AssertUnwindSafe},
+};
+use thiserror::Error;
+
+/// Type describing errors that can occur when configuring the librealsense2 logger.
+///
+/// Follows the standard pattern of errors where the enum variant describes what the low-level code
+/// was attempting to do while the string carried alongside describes the underlying error message
+/// from any C++ exceptions that occur.
+#[derive(Error, Debug)]
+pub enum LogError {
+    /// Could not log to the console.
+    #[error("Could not log to console. Type: {0}; Reason: {1}")]
+    CouldNotLogToConsole(Rs2Exception, String),
+    /// Could not log to the callback.
+    #[error("Could not log to callback. Type: {0}; Reason: {1}")]
+    CouldNotLogToCallback(Rs2Exception, String),
+}
+
+/// The type of callback invoked for every log message.
+type LogCallback = Box<dyn FnMut(Rs2LogSeverity, &str) + Send + 'static>;
+
+/// Trampoline passed to `rs2_log_to_callback`.
+///
+/// `user` must point to a live [`LogCallback`]. Messages that cannot be read are dropped. Panics in
+/// the callback are caught, since unwinding across the FFI boundary is undefined behaviour.
+unsafe extern "C" fn log_trampoline(
+    severity: sys::rs2_log_severity,
+    message_ptr: *const sys::rs2_log_message,
+    user: *mut c_void,
+) {
+    let callback = &mut *user.cast::<LogCallback>();
+
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+    let message = sys::rs2_get_raw_log_message(message_ptr, &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return;
+    }
+
+    if let Some(severity) = Rs2LogSeverity::from_i32(severity as i32) {
+        let message = CStr::from_ptr(message).to_string_lossy();
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(severity, &message)));
+    }
+}
+
+/// Log all librealsense2 messages of at least `min_severity` to the console.
+///
+/// # Errors
+///
+/// Returns [`LogError::CouldNotLogToConsole`] if the logger could not be configured.
+pub fn log_to_console(min_severity: Rs2LogSeverity) -> Result<(), LogError> {
+    unsafe {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        sys::rs2_log_to_console(min_severity as sys::rs2_log_severity, &mut err);
+        check_rs2_error!(err, LogError::CouldNotLogToConsole)
+    }
+}
+
+/// Log all librealsense2 messages of at least `min_severity` to the callback `f`.
+///
+/// The callback receives the severity and the raw message of every log entry, and is invoked from
+/// whichever thread produced the message.
+///
+/// Since the librealsense2 logger is global and provides no way to unregister a callback, the
+/// callback is kept alive for the remainder of the program.
+///
+/// # Errors
+///
+/// Returns [`LogError::CouldNotLogToCallback`] if the callback could not be registered.
+pub fn log_to_callback(
+    min_severity: Rs2LogSeverity,
+    f: impl FnMut(Rs2LogSeverity, &str) + Send + 'static,
+) -> Result<(), LogError> {
+    let callback: Box<LogCallback> = Box::new(Box::new(f));
+    let callback_ptr = Box::into_raw(callback);
+
+    unsafe {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        sys::rs2_log_to_callback(
+            min_severity as sys::rs2_log_severity,
+            Some(log_trampoline),
+            callback_ptr.cast::<c_void>(),
+            &mut err,
+        );
+
+        if err.as_ref().is_some() {
+            // The callback was never registered, so it is safe to reclaim it here.
+            drop(Box::from_raw(callback_ptr));
+        }
+        check_rs2_error!(err, LogError::CouldNotLogToCallback)
+    }
+}
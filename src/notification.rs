@@ -0,0 +1,165 @@
+//! Type for representing notifications raised by a sensor.
+//!
+//! Sensors may notify the application about events that occur while streaming, such as frames
+//! timing out, corrupted frames, or hardware errors. These notifications are delivered to a
+//! callback registered with
+//! [`Sensor::set_notifications_callback`](crate::sensor::Sensor::set_notifications_callback).
+
+use crate::{
+    check_rs2_error,
+    kind::{Rs2Exception, Rs2LogSeverity, Rs2NotificationCategory},
+};
+#[allow(unused_imports)]
+use num_traits::FromPrimitive;
+
+use realsense_sys as sys;
+use std::{
+    convert::TryFrom,
+    ffi::CStr,
+    os::raw::c_void,
+    panic::{self, AssertUnwindSafe},
+    ptr::NonNull,
+};
+use thiserror::Error;
+
+/// Type describing errors that can occur when trying to read a notification.
+///
+/// Follows the standard pattern of errors where the enum variant describes what the low-level code
+/// was attempting to do while the string carried alongside describes the underlying error message
+/// from any C++ exceptions that occur.
+#[derive(Error, Debug)]
+pub enum NotificationConstructionError {
+    /// Could not get the notification description.
+    #[error("Could not get notification description. Type: {0}; Reason: {1}")]
+    CouldNotGetDescription(Rs2Exception, String),
+    /// Could not get the notification timestamp.
+    #[error("Could not get notification timestamp. Type: {0}; Reason: {1}")]
+    CouldNotGetTimestamp(Rs2Exception, String),
+    /// Could not get the notification severity.
+    #[error("Could not get notification severity. Type: {0}; Reason: {1}")]
+    CouldNotGetSeverity(Rs2Exception, String),
+    /// Could not get the notification category.
+    #[error("Could not get notification category. Type: {0}; Reason: {1}")]
+    CouldNotGetCategory(Rs2Exception, String),
+    /// The notification severity is not known to these bindings.
+    #[error("Unknown notification severity: {0}")]
+    UnknownSeverity(i32),
+    /// The notification category is not known to these bindings.
+    #[error("Unknown notification category: {0}")]
+    UnknownCategory(i32),
+}
+
+/// The type of callback invoked for every notification raised by a sensor.
+pub(crate) type NotificationCallback = Box<dyn FnMut(Notification) + Send + 'static>;
+
+/// A notification raised by a sensor.
+///
+/// The underlying `rs2_notification` is only valid for the duration of the callback it is
+/// delivered to, so all of its data is copied out when the notification is constructed.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// The human-readable description of the notification.
+    description: String,
+    /// The timestamp of the notification.
+    timestamp: f64,
+    /// The severity of the notification.
+    severity: Rs2LogSeverity,
+    /// The category of the notification.
+    category: Rs2NotificationCategory,
+}
+
+impl TryFrom<NonNull<sys::rs2_notification>> for Notification {
+    type Error = NotificationConstructionError;
+
+    /// Attempt to construct a notification from a non-null pointer to `rs2_notification`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NotificationConstructionError`] describing which field of the notification
+    /// could not be read, or holds a severity or category that these bindings do not know (e.g.
+    /// one added by a newer librealsense2).
+    fn try_from(notification_ptr: NonNull<sys::rs2_notification>) -> Result<Self, Self::Error> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+            let description =
+                sys::rs2_get_notification_description(notification_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, NotificationConstructionError::CouldNotGetDescription)?;
+            let description = CStr::from_ptr(description).to_string_lossy().into_owned();
+
+            let timestamp =
+                sys::rs2_get_notification_timestamp(notification_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, NotificationConstructionError::CouldNotGetTimestamp)?;
+
+            let severity = sys::rs2_get_notification_severity(notification_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, NotificationConstructionError::CouldNotGetSeverity)?;
+
+            let category = sys::rs2_get_notification_category(notification_ptr.as_ptr(), &mut err);
+            check_rs2_error!(err, NotificationConstructionError::CouldNotGetCategory)?;
+
+            let severity = Rs2LogSeverity::from_i32(severity as i32).ok_or(
+                NotificationConstructionError::UnknownSeverity(severity as i32),
+            )?;
+            let category = Rs2NotificationCategory::from_i32(category as i32).ok_or(
+                NotificationConstructionError::UnknownCategory(category as i32),
+            )?;
+
+            Ok(Notification {
+                description,
+                timestamp,
+                severity,
+                category,
+            })
+        }
+    }
+}
+
+impl Notification {
+    /// Get the human-readable description of the notification.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get the timestamp of the notification.
+    pub fn timestamp(&self) -> f64 {
+        self.timestamp
+    }
+
+    /// Get the severity of the notification.
+    pub fn severity(&self) -> Rs2LogSeverity {
+        self.severity
+    }
+
+    /// Get the category of the notification.
+    pub fn category(&self) -> Rs2NotificationCategory {
+        self.category
+    }
+}
+
+/// Trampoline passed to `rs2_set_notifications_callback`.
+///
+/// `user` must point to a live [`NotificationCallback`]. Notifications that cannot be read are
+/// dropped. Panics in the callback are caught, since unwinding across the FFI boundary is
+/// undefined behaviour.
+pub(crate) unsafe extern "C" fn notification_trampoline(
+    notification_ptr: *mut sys::rs2_notification,
+    user: *mut c_void,
+) {
+    let callback = &mut *user.cast::<NotificationCallback>();
+
+    if let Some(notification) =
+        NonNull::new(notification_ptr).and_then(|ptr| Notification::try_from(ptr).ok())
+    {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(notification)));
+    }
+}
+
+/// Callback passed to `rs2_set_notifications_callback` to unregister a previous callback.
+///
+/// librealsense2 does not accept a null callback, so this callback which ignores every
+/// notification is registered instead.
+pub(crate) unsafe extern "C" fn ignore_notification(
+    _notification_ptr: *mut sys::rs2_notification,
+    _user: *mut c_void,
+) {
+}
@@ -126,7 +126,11 @@ impl ActivePipeline {
             check_rs2_error!(err, FrameWaitError::DidErrorDuringFrameWait)?;
 
             if did_get_frame != 0 {
-                Ok(CompositeFrame::from(NonNull::new(frame).unwrap()))
+                // The pipeline always delivers framesets, so there is no need to check the
+                // extension of the frame.
+                Ok(CompositeFrame {
+                    ptr: NonNull::new(frame).unwrap(),
+                })
             } else {
                 Err(FrameWaitError::DidTimeoutBeforeFrameArrival)
             }
@@ -155,9 +159,9 @@ impl ActivePipeline {
             check_rs2_error!(err, FrameWaitError::DidErrorDuringFramePoll)?;
 
             if did_get_frame != 0 {
-                Ok(Poll::Ready(CompositeFrame::from(
-                    NonNull::new(frame_ptr).unwrap(),
-                )))
+                Ok(Poll::Ready(CompositeFrame {
+                    ptr: NonNull::new(frame_ptr).unwrap(),
+                }))
             } else {
                 Ok(Poll::Pending)
             }
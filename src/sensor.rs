@@ -19,18 +19,25 @@ use crate::{
     check_rs2_error,
     device::{Device, DeviceConstructionError},
     kind::{
-        OptionSetError, Rs2CameraInfo, Rs2Exception, Rs2Extension, Rs2Option, Rs2OptionRange,
-        SENSOR_EXTENSIONS,
+        typed_option::VisualPreset, OptionGetError, OptionSetError, Rs2CameraInfo, Rs2Exception,
+        Rs2Extension, Rs2Format, Rs2Option, Rs2OptionRange, Rs2Rs400VisualPreset, Rs2StreamKind,
+        TypedOption, SENSOR_EXTENSIONS,
+    },
+    notification::{
+        ignore_notification, notification_trampoline, Notification, NotificationCallback,
     },
     stream_profile::StreamProfile,
 };
 use anyhow::Result;
 use realsense_sys as sys;
 use std::{
+    collections::HashMap,
     convert::{From, TryInto},
     ffi::CStr,
     mem::MaybeUninit,
     ptr::NonNull,
+    sync::Arc,
+    time::Duration,
 };
 use thiserror::Error;
 
@@ -58,6 +65,19 @@ pub enum RoiSetError {
     CouldNotSetRoi(Rs2Exception, String),
 }
 
+/// Type describing errors that can occur when trying to set the notifications callback of a
+/// sensor.
+///
+/// Follows the standard pattern of errors where the enum variant describes what the low-level code
+/// was attempting to do while the string carried alongside describes the underlying error message
+/// from any C++ exceptions that occur.
+#[derive(Error, Debug)]
+pub enum NotificationsCallbackError {
+    /// Could not set the notifications callback for sensor.
+    #[error("Could not set notifications callback for sensor. Type: {0}; Reason: {1}")]
+    CouldNotSetNotificationsCallback(Rs2Exception, String),
+}
+
 /// Type for holding sensor-related data.
 ///
 /// A sensor in librealsense2 corresponds to a physical component on the unit in some way, shape,
@@ -70,6 +90,23 @@ pub enum RoiSetError {
 /// 1. From the device's [sensor list](crate::device::Device::sensors)
 /// 2. By getting the sensor that [corresponds to a given frame](crate::frame::FrameEx::sensor)
 pub struct Sensor {
+    /// The underlying non-null sensor pointer.
+    ///
+    /// This is a copy of the pointer held by `handle`, kept for convenience.
+    sensor_ptr: NonNull<sys::rs2_sensor>,
+    /// Reference-counted handle that owns the sensor pointer.
+    ///
+    /// Clones of a sensor share this handle, so that the sensor pointer is deleted exactly once,
+    /// when the last clone is dropped.
+    handle: Arc<SensorHandle>,
+    /// The notifications callback registered with this sensor, if any.
+    ///
+    /// This is double-boxed so that the pointer handed to librealsense2 stays valid and thin.
+    notifications_callback: Option<Box<NotificationCallback>>,
+}
+
+/// Owner of the underlying sensor pointer, shared between clones of a [`Sensor`].
+struct SensorHandle {
     /// The underlying non-null sensor pointer.
     ///
     /// This should not be deleted unless the sensor was constructed via `rs2_create_sensor`
@@ -78,7 +115,7 @@ pub struct Sensor {
     should_drop: bool,
 }
 
-impl Drop for Sensor {
+impl Drop for SensorHandle {
     fn drop(&mut self) {
         unsafe {
             if self.should_drop {
@@ -88,15 +125,67 @@ impl Drop for Sensor {
     }
 }
 
+// The handle is only used to delete the sensor pointer once the last clone is dropped, which
+// librealsense2 allows from any thread.
+unsafe impl Send for SensorHandle {}
+unsafe impl Sync for SensorHandle {}
+
+impl Drop for Sensor {
+    fn drop(&mut self) {
+        unsafe {
+            if self.notifications_callback.is_some() {
+                let mut err = std::ptr::null_mut::<sys::rs2_error>();
+                sys::rs2_set_notifications_callback(
+                    self.sensor_ptr.as_ptr(),
+                    Some(ignore_notification),
+                    std::ptr::null_mut(),
+                    &mut err,
+                );
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                }
+            }
+        }
+    }
+}
+
 unsafe impl Send for Sensor {}
 
+impl std::fmt::Debug for Sensor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The notifications callback is an opaque closure, so only report whether one is set.
+        f.debug_struct("Sensor")
+            .field("sensor_ptr", &self.sensor_ptr)
+            .field(
+                "has_notifications_callback",
+                &self.notifications_callback.is_some(),
+            )
+            .finish()
+    }
+}
+
+impl Clone for Sensor {
+    /// Create another handle to the same sensor.
+    ///
+    /// The underlying sensor pointer is shared, and only deleted once all clones have been
+    /// dropped. Notification callbacks are not shared: the clone starts without one, and a
+    /// callback set through one clone is unregistered when that clone is dropped.
+    fn clone(&self) -> Self {
+        Sensor {
+            sensor_ptr: self.sensor_ptr,
+            handle: Arc::clone(&self.handle),
+            notifications_callback: None,
+        }
+    }
+}
+
 impl std::convert::From<NonNull<sys::rs2_sensor>> for Sensor {
     /// Attempt to construct a Sensor from a non-null pointer to `rs2_sensor`.
     fn from(sensor_ptr: NonNull<sys::rs2_sensor>) -> Self {
-        Sensor {
+        Sensor::from_handle(SensorHandle {
             sensor_ptr,
             should_drop: false,
-        }
+        })
     }
 }
 
@@ -133,9 +222,19 @@ impl Sensor {
             check_rs2_error!(err, SensorConstructionError::CouldNotGetSensorFromList)?;
 
             let nonnull_ptr = NonNull::new(sensor_ptr).unwrap();
-            let mut sensor = Sensor::from(nonnull_ptr);
-            sensor.should_drop = true;
-            Ok(sensor)
+            Ok(Sensor::from_handle(SensorHandle {
+                sensor_ptr: nonnull_ptr,
+                should_drop: true,
+            }))
+        }
+    }
+
+    /// Construct a sensor that takes ownership of `handle`.
+    fn from_handle(handle: SensorHandle) -> Self {
+        Sensor {
+            sensor_ptr: handle.sensor_ptr,
+            handle: Arc::new(handle),
+            notifications_callback: None,
         }
     }
 
@@ -159,6 +258,32 @@ impl Sensor {
         }
     }
 
+    /// Read the calibration table of the parent device.
+    ///
+    /// See [`Device::calibration_table`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceConstructionError::CouldNotCreateDeviceFromSensor`] if the parent device
+    /// cannot be obtained, or any [`CalibrationTableError`](crate::device::CalibrationTableError)
+    /// raised while reading the table.
+    pub fn calibration_table(&self) -> Result<Vec<u8>> {
+        Ok(self.device()?.calibration_table()?)
+    }
+
+    /// Write a calibration table to the parent device.
+    ///
+    /// See [`Device::set_calibration_table`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeviceConstructionError::CouldNotCreateDeviceFromSensor`] if the parent device
+    /// cannot be obtained, or any [`CalibrationTableError`](crate::device::CalibrationTableError)
+    /// raised while writing the table.
+    pub fn set_calibration_table(&mut self, table: &[u8]) -> Result<()> {
+        Ok(self.device()?.set_calibration_table(table)?)
+    }
+
     /// Get sensor extension.
     pub fn extension(&self) -> Rs2Extension {
         let ext = SENSOR_EXTENSIONS
@@ -186,12 +311,47 @@ impl Sensor {
     /// Get the value associated with the provided Rs2Option for the sensor.
     ///
     /// Returns An `f32` value corresponding to that option within the librealsense2 library, or None
-    /// if the option is not supported.
+    /// if the option is not supported. Use [`Sensor::try_get_option`] to tell an unsupported
+    /// option apart from an option that could not be read.
     pub fn get_option(&self, option: Rs2Option) -> Option<f32> {
         if !self.supports_option(option) {
             return None;
         }
 
+        self.get_option_unchecked(option)
+    }
+
+    /// Get the value associated with the provided Rs2Option for the sensor, reporting any error
+    /// that occurs.
+    ///
+    /// Unlike [`Sensor::get_option`], this distinguishes an unsupported option (`Ok(None)`) from
+    /// a supported option that could not be read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionGetError::CouldNotGetOption`] if the option is supported but could not be
+    /// read, carrying the exception raised by librealsense2.
+    pub fn try_get_option(&self, option: Rs2Option) -> Result<Option<f32>, OptionGetError> {
+        if !self.supports_option(option) {
+            return Ok(None);
+        }
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let val = sys::rs2_get_option(
+                self.sensor_ptr.as_ptr().cast::<sys::rs2_options>(),
+                #[allow(clippy::useless_conversion)]
+                (option as i32).try_into().unwrap(),
+                &mut err,
+            );
+            check_rs2_error!(err, OptionGetError::CouldNotGetOption)?;
+
+            Ok(Some(val))
+        }
+    }
+
+    /// Get the value of `option`, without first checking whether it is supported.
+    fn get_option_unchecked(&self, option: Rs2Option) -> Option<f32> {
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
             let val = sys::rs2_get_option(
@@ -233,6 +393,15 @@ impl Sensor {
             return Err(OptionSetError::OptionIsReadOnly);
         }
 
+        self.set_option_unchecked(option, value)
+    }
+
+    /// Set the value of `option`, without first checking whether it is supported and writable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::CouldNotSetOption`] if the option could not be set.
+    fn set_option_unchecked(&self, option: Rs2Option, value: f32) -> Result<(), OptionSetError> {
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
             sys::rs2_set_option(
@@ -248,9 +417,137 @@ impl Sensor {
         }
     }
 
+    /// Get the value of the typed option `T` for the sensor.
+    ///
+    /// Returns `None` if the option is not supported, or if its value does not correspond to any
+    /// value of [`T::Value`](TypedOption::Value). See [`Sensor::get_option`].
+    pub fn get_typed<T: TypedOption>(&self) -> Option<T::Value> {
+        self.get_option(T::option()).and_then(T::decode)
+    }
+
+    /// Sets the value of the typed option `T` for the sensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Sensor::set_option`].
+    pub fn set_typed<T: TypedOption>(&mut self, value: T::Value) -> Result<(), OptionSetError> {
+        self.set_option(T::option(), T::encode(value))
+    }
+
+    /// Apply a visual preset to a D400 series depth sensor.
+    ///
+    /// This is a shorthand for setting the [`VisualPreset`] typed option. Note that presets that
+    /// are applied this way are not retained when the device is reset.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Sensor::set_option`], e.g.
+    /// [`OptionSetError::OptionNotSupported`] if the sensor is not a D400 series depth sensor.
+    pub fn set_visual_preset(
+        &mut self,
+        preset: Rs2Rs400VisualPreset,
+    ) -> Result<(), OptionSetError> {
+        self.set_typed::<VisualPreset>(preset)
+    }
+
+    /// Get a handle for repeatedly reading or setting `option` on the sensor.
+    ///
+    /// Whether the option is supported and whether it is read-only is checked once, here, rather
+    /// than on every access as [`Sensor::get_option`] and [`Sensor::set_option`] do. This halves
+    /// the number of calls into librealsense2 when an option is accessed in a tight loop, e.g. to
+    /// set the laser power every frame.
+    ///
+    /// Returns `None` if the option is not supported by the sensor.
+    pub fn option_handle(&self, option: Rs2Option) -> Option<OptionHandle<'_>> {
+        if !self.supports_option(option) {
+            return None;
+        }
+
+        Some(OptionHandle {
+            sensor: self,
+            option,
+            is_read_only: self.is_option_read_only(option),
+        })
+    }
+
+    /// Sets the manual exposure of the sensor, in microseconds.
+    ///
+    /// The value is clamped to the range the sensor supports for [`Rs2Option::Exposure`]. Returns
+    /// the value that was actually applied.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`].
+    pub fn set_exposure(&mut self, usec: f32) -> Result<f32, OptionSetError> {
+        self.set_option_clamped(Rs2Option::Exposure, usec)
+    }
+
+    /// Sets the gain of the sensor.
+    ///
+    /// The value is clamped to the range the sensor supports for [`Rs2Option::Gain`]. Returns the
+    /// value that was actually applied.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`].
+    pub fn set_gain(&mut self, gain: f32) -> Result<f32, OptionSetError> {
+        self.set_option_clamped(Rs2Option::Gain, gain)
+    }
+
+    /// Enables or disables auto exposure on the sensor.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`].
+    pub fn enable_auto_exposure(&mut self, enabled: bool) -> Result<(), OptionSetError> {
+        self.set_option(
+            Rs2Option::EnableAutoExposure,
+            if enabled { 1.0 } else { 0.0 },
+        )
+    }
+
+    /// Sets `option` to the value nearest to `value` that the sensor accepts.
+    ///
+    /// The value is snapped to the range and step supported by the sensor (see
+    /// [`Rs2OptionRange::snap`]), so that e.g. setting 0.33 on an option with a step of 0.1 writes
+    /// 0.3. Returns the value that was actually written.
+    ///
+    /// # Errors
+    ///
+    /// See [`Sensor::set_option`].
+    pub fn set_option_snapped(
+        &mut self,
+        option: Rs2Option,
+        value: f32,
+    ) -> Result<f32, OptionSetError> {
+        let range = self
+            .get_option_range(option)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        let value = range.snap(value);
+
+        self.set_option(option, value)?;
+        Ok(value)
+    }
+
+    /// Sets `option` to `value`, clamped to the range supported by the sensor.
+    ///
+    /// Returns the value that was actually applied.
+    fn set_option_clamped(&mut self, option: Rs2Option, value: f32) -> Result<f32, OptionSetError> {
+        let range = self
+            .get_option_range(option)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+        let value = value.max(range.min).min(range.max);
+
+        self.set_option(option, value)?;
+        Ok(value)
+    }
+
     /// Gets the range for a given option.
     ///
-    /// Returns some option range if the sensor supports the option, else `None`.
+    /// Returns some option range if the sensor supports the option, else `None`. This works for the
+    /// options of any sensor, including motion sensors, whose options may accept only a single
+    /// value. See [`Rs2OptionRange::is_boolean`] and [`Rs2OptionRange::is_continuous`] to tell how
+    /// an option is best presented.
     pub fn get_option_range(&self, option: Rs2Option) -> Option<Rs2OptionRange> {
         if !self.supports_option(option) {
             return None;
@@ -289,6 +586,41 @@ impl Sensor {
         }
     }
 
+    /// Resets `option` to the default value reported by its [range](Sensor::get_option_range).
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Sensor::set_option`].
+    pub fn reset_option_to_default(&mut self, option: Rs2Option) -> Result<(), OptionSetError> {
+        let range = self
+            .get_option_range(option)
+            .ok_or(OptionSetError::OptionNotSupported)?;
+
+        self.set_option(option, range.default)
+    }
+
+    /// Resets every supported, writable option of the sensor to its default value.
+    ///
+    /// All options are attempted, even if resetting some of them fails. Setting some options can
+    /// legitimately fail depending on the others, e.g. the manual exposure cannot be set while
+    /// auto exposure is enabled. Returns the options that could not be reset, along with the
+    /// reason why.
+    pub fn reset_all_options_to_default(&mut self) -> Vec<(Rs2Option, OptionSetError)> {
+        let writable_options: Vec<Rs2Option> = (0..sys::rs2_option_RS2_OPTION_COUNT as i32)
+            .filter_map(Rs2Option::from_i32)
+            .filter(|&option| self.supports_option(option) && !self.is_option_read_only(option))
+            .collect();
+
+        writable_options
+            .into_iter()
+            .filter_map(|option| {
+                self.reset_option_to_default(option)
+                    .err()
+                    .map(|e| (option, e))
+            })
+            .collect()
+    }
+
     /// Predicate for determining if this sensor supports a given option
     ///
     /// Returns true iff the option is supported by this sensor.
@@ -342,41 +674,159 @@ impl Sensor {
     /// Returns a vector containing all the stream profiles associated with the sensor. The vector
     /// will have a length of zero if an error occurs while getting the stream profiles.
     pub fn stream_profiles(&self) -> Vec<StreamProfile> {
-        let mut profiles = Vec::new();
         unsafe {
             let mut err = std::ptr::null_mut::<sys::rs2_error>();
             let profiles_ptr = sys::rs2_get_stream_profiles(self.sensor_ptr.as_ptr(), &mut err);
             if err.as_ref().is_some() {
                 sys::rs2_free_error(err);
-                return profiles;
+                return Vec::new();
             }
 
-            let nonnull_profiles_ptr = NonNull::new(profiles_ptr).unwrap();
-            let len = sys::rs2_get_stream_profiles_count(nonnull_profiles_ptr.as_ptr(), &mut err);
+            take_stream_profiles(profiles_ptr, |_| true)
+        }
+    }
 
+    /// Get the stream profiles of this sensor that match the given stream kind, format and
+    /// framerate.
+    ///
+    /// Each criterion that is `None` matches any profile. Profiles are checked before they are
+    /// constructed, so profiles that do not match are never allocated. The vector will have a
+    /// length of zero if no profile matches or if an error occurs while getting the stream
+    /// profiles.
+    // `Option::is_none_or` would require Rust 1.82.
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn stream_profiles_matching(
+        &self,
+        kind: Option<Rs2StreamKind>,
+        format: Option<Rs2Format>,
+        fps: Option<i32>,
+    ) -> Vec<StreamProfile> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let profiles_ptr = sys::rs2_get_stream_profiles(self.sensor_ptr.as_ptr(), &mut err);
             if err.as_ref().is_some() {
                 sys::rs2_free_error(err);
-                sys::rs2_delete_stream_profiles_list(nonnull_profiles_ptr.as_ptr());
-                return profiles;
+                return Vec::new();
             }
 
-            for i in 0..len {
-                match StreamProfile::try_create(&nonnull_profiles_ptr, i) {
-                    Ok(s) => {
-                        profiles.push(s);
-                    }
-                    Err(_) => {
-                        continue;
-                    }
+            take_stream_profiles(profiles_ptr, |profile_ptr| {
+                let mut err = std::ptr::null_mut::<sys::rs2_error>();
+                let mut stream = MaybeUninit::uninit();
+                let mut profile_format = MaybeUninit::uninit();
+                let mut index = MaybeUninit::uninit();
+                let mut unique_id = MaybeUninit::uninit();
+                let mut framerate = MaybeUninit::uninit();
+
+                sys::rs2_get_stream_profile_data(
+                    profile_ptr,
+                    stream.as_mut_ptr(),
+                    profile_format.as_mut_ptr(),
+                    index.as_mut_ptr(),
+                    unique_id.as_mut_ptr(),
+                    framerate.as_mut_ptr(),
+                    &mut err,
+                );
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    return false;
                 }
+
+                kind.map_or(true, |k| k as i32 == stream.assume_init() as i32)
+                    && format.map_or(true, |f| f as i32 == profile_format.assume_init() as i32)
+                    && fps.map_or(true, |fps| fps == framerate.assume_init())
+            })
+        }
+    }
+
+    /// Get a list of the stream profiles this sensor is currently streaming.
+    ///
+    /// Returns a vector containing the stream profiles the sensor has been opened with and is
+    /// streaming. The vector will have a length of zero if the sensor is not streaming or if an
+    /// error occurs while getting the stream profiles.
+    pub fn active_streams(&self) -> Vec<StreamProfile> {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let profiles_ptr = sys::rs2_get_active_streams(self.sensor_ptr.as_ptr(), &mut err);
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return Vec::new();
             }
-            sys::rs2_delete_stream_profiles_list(nonnull_profiles_ptr.as_ptr());
+
+            take_stream_profiles(profiles_ptr, |_| true)
         }
-        profiles
+    }
+
+    /// Get the first stream profile this sensor is currently streaming.
+    ///
+    /// Returns `None` if the sensor is not streaming. See [`Sensor::active_streams`].
+    pub fn first_active_profile(&self) -> Option<StreamProfile> {
+        self.active_streams().into_iter().next()
+    }
+
+    /// Predicate for whether the sensor is currently opened with any stream profiles, e.g. by a
+    /// running pipeline.
+    ///
+    /// This is based on [`Sensor::active_streams`], so it also returns `false` if an error occurs
+    /// while getting the stream profiles. librealsense2 does not report separately whether an
+    /// opened sensor has been started, but sensors opened by a pipeline are started right away.
+    pub fn is_open(&self) -> bool {
+        !self.active_streams().is_empty()
     }
 
     // fn recommended_processing_blocks(&self) -> Vec<ProcessingBlock>{}
 
+    /// Predicate for whether the processing block with the given extension, e.g.
+    /// [`Rs2Extension::SpatialFilter`], is recommended for the frames of this sensor.
+    ///
+    /// Processing blocks which are not recommended for a sensor may silently pass its frames
+    /// through unchanged. Returns false if an error occurs while getting the recommended processing
+    /// blocks.
+    pub fn supports_processing_block(&self, block_kind: Rs2Extension) -> bool {
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            let list_ptr =
+                sys::rs2_get_recommended_processing_blocks(self.sensor_ptr.as_ptr(), &mut err);
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                return false;
+            }
+
+            let count = sys::rs2_get_recommended_processing_blocks_count(list_ptr, &mut err);
+            if err.as_ref().is_some() {
+                sys::rs2_free_error(err);
+                sys::rs2_delete_recommended_processing_blocks(list_ptr);
+                return false;
+            }
+
+            let is_supported = (0..count).any(|index| {
+                let mut err = std::ptr::null_mut::<sys::rs2_error>();
+                let block_ptr = sys::rs2_get_processing_block(list_ptr, index, &mut err);
+                if err.as_ref().is_some() {
+                    sys::rs2_free_error(err);
+                    return false;
+                }
+
+                let is_extendable = sys::rs2_is_processing_block_extendable_to(
+                    block_ptr,
+                    #[allow(clippy::useless_conversion)]
+                    (block_kind as i32).try_into().unwrap(),
+                    &mut err,
+                );
+                sys::rs2_delete_processing_block(block_ptr);
+
+                if err.as_ref().is_none() {
+                    is_extendable != 0
+                } else {
+                    sys::rs2_free_error(err);
+                    false
+                }
+            });
+
+            sys::rs2_delete_recommended_processing_blocks(list_ptr);
+            is_supported
+        }
+    }
+
     /// Gets the value associated with the provided camera info key from the sensor.
     ///
     /// Returns some value corresponding to the camera info requested if this sensor supports that
@@ -427,6 +877,51 @@ impl Sensor {
         }
     }
 
+    /// Gets every camera info value the sensor supports.
+    ///
+    /// Values that are not valid UTF-8 are converted lossily.
+    pub fn info_all(&self) -> HashMap<Rs2CameraInfo, String> {
+        (0..sys::rs2_camera_info_RS2_CAMERA_INFO_COUNT as i32)
+            .filter_map(Rs2CameraInfo::from_i32)
+            .filter_map(|camera_info| {
+                self.info(camera_info)
+                    .map(|val| (camera_info, val.to_string_lossy().into_owned()))
+            })
+            .collect()
+    }
+
+    /// Gets the serial number of the sensor.
+    ///
+    /// Returns `None` if the sensor does not provide [`Rs2CameraInfo::SerialNumber`].
+    pub fn serial_number(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::SerialNumber)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the name of the sensor.
+    ///
+    /// Returns `None` if the sensor does not provide [`Rs2CameraInfo::Name`].
+    pub fn name(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::Name)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the physical port of the sensor.
+    ///
+    /// Returns `None` if the sensor does not provide [`Rs2CameraInfo::PhysicalPort`].
+    pub fn physical_port(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::PhysicalPort)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
+    /// Gets the USB type descriptor (e.g. "3.2") of the sensor.
+    ///
+    /// Returns `None` if the sensor does not provide [`Rs2CameraInfo::UsbTypeDescriptor`].
+    pub fn usb_type(&self) -> Option<String> {
+        self.info(Rs2CameraInfo::UsbTypeDescriptor)
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
     /// Gets the auto exposure's region of interest for the sensor.
     ///
     /// Returns the region of interest for the auto exposure or None
@@ -485,4 +980,192 @@ impl Sensor {
             check_rs2_error!(err, RoiSetError::CouldNotSetRoi)
         }
     }
+
+    /// Sets the auto exposure's region of interest from fractions of a `width` by `height` frame.
+    ///
+    /// The fractions are converted to pixels with [`Rs2Roi::from_normalized`], so e.g. metering
+    /// the center 50% of the frame (`0.25, 0.25, 0.75, 0.75`) does not depend on the resolution
+    /// of the stream. See [`set_region_of_interest`](Sensor::set_region_of_interest) for the
+    /// known issues of setting the region of interest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RoiSetError::CouldNotSetRoi`] if setting the region of interest failed.
+    pub fn set_region_of_interest_normalized(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), RoiSetError> {
+        self.set_region_of_interest(Rs2Roi::from_normalized(
+            left, top, right, bottom, width, height,
+        ))
+    }
+
+    /// Sets a callback that is invoked for every notification raised by the sensor.
+    ///
+    /// Notifications report events such as frame timeouts, corrupted frames or hardware errors
+    /// that occur while streaming. The callback is invoked from a librealsense2 thread. It is kept
+    /// alive by the sensor, replaces any previously set callback, and is unregistered when the
+    /// sensor is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotificationsCallbackError::CouldNotSetNotificationsCallback`] if the callback
+    /// could not be registered with librealsense2.
+    pub fn set_notifications_callback(
+        &mut self,
+        f: impl FnMut(Notification) + Send + 'static,
+    ) -> Result<(), NotificationsCallbackError> {
+        let mut callback: Box<NotificationCallback> = Box::new(Box::new(f));
+
+        unsafe {
+            let mut err = std::ptr::null_mut::<sys::rs2_error>();
+            sys::rs2_set_notifications_callback(
+                self.sensor_ptr.as_ptr(),
+                Some(notification_trampoline),
+                (callback.as_mut() as *mut NotificationCallback).cast::<std::os::raw::c_void>(),
+                &mut err,
+            );
+            check_rs2_error!(
+                err,
+                NotificationsCallbackError::CouldNotSetNotificationsCallback
+            )?;
+        }
+
+        self.notifications_callback = Some(callback);
+        Ok(())
+    }
+
+    /// Sets the auto exposure's region of interest to `roi`, retrying on failure.
+    ///
+    /// This encodes the workaround described in the known issues of
+    /// [`set_region_of_interest`](Sensor::set_region_of_interest): the command is repeated up to
+    /// `attempts` times, sleeping for `delay` between each failed attempt. The region of interest
+    /// is always attempted to be set at least once, even if `attempts` is zero.
+    ///
+    /// Returns null tuple as soon as the region of interest is set successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last [`RoiSetError::CouldNotSetRoi`] if every attempt failed.
+    pub fn set_region_of_interest_retry(
+        &mut self,
+        roi: Rs2Roi,
+        attempts: usize,
+        delay: Duration,
+    ) -> Result<(), RoiSetError> {
+        let mut result = self.set_region_of_interest(roi.clone());
+
+        for _ in 1..attempts {
+            if result.is_ok() {
+                break;
+            }
+            std::thread::sleep(delay);
+            result = self.set_region_of_interest(roi.clone());
+        }
+        result
+    }
+}
+
+/// A handle to an option that is known to be supported by a sensor.
+///
+/// Obtained via [`Sensor::option_handle`]. Accessing the option through the handle skips the
+/// support and read-only checks that [`Sensor::get_option`] and [`Sensor::set_option`] perform.
+#[derive(Debug)]
+pub struct OptionHandle<'a> {
+    /// The sensor the option belongs to.
+    sensor: &'a Sensor,
+    /// The option being accessed.
+    option: Rs2Option,
+    /// Whether the option was read-only when the handle was created.
+    is_read_only: bool,
+}
+
+impl OptionHandle<'_> {
+    /// Get the option this handle accesses.
+    pub fn option(&self) -> Rs2Option {
+        self.option
+    }
+
+    /// Predicate for whether the option was read-only when the handle was created.
+    pub fn is_read_only(&self) -> bool {
+        self.is_read_only
+    }
+
+    /// Get the current value of the option.
+    ///
+    /// Returns `None` if the value could not be read (e.g. if the device was disconnected).
+    pub fn get(&self) -> Option<f32> {
+        self.sensor.get_option_unchecked(self.option)
+    }
+
+    /// Set the value of the option.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionSetError::OptionIsReadOnly`] if the option was read-only when the handle
+    /// was created.
+    ///
+    /// Returns [`OptionSetError::CouldNotSetOption`] if the option could not be set (invalid
+    /// value, internal exception, etc.).
+    pub fn set(&self, value: f32) -> Result<(), OptionSetError> {
+        if self.is_read_only {
+            return Err(OptionSetError::OptionIsReadOnly);
+        }
+
+        self.sensor.set_option_unchecked(self.option, value)
+    }
+}
+
+/// Collect the stream profiles in a stream profile list, and delete the list afterwards.
+///
+/// Only profiles for which `filter` returns `true` are collected; `filter` is passed the profile
+/// pointer in the list, before the profile is constructed. Profiles that cannot be created are
+/// skipped. Returns an empty vector if the length of the list cannot be obtained.
+///
+/// # Safety
+///
+/// `profiles_ptr` must be a valid stream profile list that is not used after this call.
+unsafe fn take_stream_profiles(
+    profiles_ptr: *mut sys::rs2_stream_profile_list,
+    filter: impl Fn(*const sys::rs2_stream_profile) -> bool,
+) -> Vec<StreamProfile> {
+    let mut profiles = Vec::new();
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+    let nonnull_profiles_ptr = NonNull::new(profiles_ptr).unwrap();
+    let len = sys::rs2_get_stream_profiles_count(nonnull_profiles_ptr.as_ptr(), &mut err);
+
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        sys::rs2_delete_stream_profiles_list(nonnull_profiles_ptr.as_ptr());
+        return profiles;
+    }
+
+    for i in 0..len {
+        let profile_ptr = sys::rs2_get_stream_profile(nonnull_profiles_ptr.as_ptr(), i, &mut err);
+        if err.as_ref().is_some() {
+            sys::rs2_free_error(err);
+            err = std::ptr::null_mut();
+            continue;
+        }
+        if !filter(profile_ptr) {
+            continue;
+        }
+
+        match StreamProfile::try_create(&nonnull_profiles_ptr, i) {
+            Ok(s) => {
+                profiles.push(s);
+            }
+            Err(_) => {
+                continue;
+            }
+        }
+    }
+    sys::rs2_delete_stream_profiles_list(nonnull_profiles_ptr.as_ptr());
+    profiles
 }
@@ -42,12 +42,18 @@ use num_traits::FromPrimitive;
 use crate::{
     base::{Rs2Extrinsics, Rs2Intrinsics, Rs2MotionDeviceIntrinsics},
     check_rs2_error,
-    kind::{Rs2Exception, Rs2Format, Rs2StreamKind},
+    kind::{Rs2Exception, Rs2Extension, Rs2Format, Rs2StreamKind},
 };
 use anyhow::Result;
 
 use realsense_sys as sys;
-use std::{convert::TryFrom, mem::MaybeUninit, ptr::NonNull};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ptr::NonNull,
+};
 use thiserror::Error;
 
 /// Type describing errors that can occur when trying to construct a stream profile.
@@ -145,6 +151,8 @@ pub struct StreamProfile {
     unique_id: i32,
     /// The framerate of the stream (how fast it outputs data)
     framerate: i32,
+    /// The (width, height) of the stream in pixels, for video streams only.
+    resolution: Option<(usize, usize)>,
     /// Whether or not the stream is a default stream.
     is_default: bool,
     /// Whether or not to drop the profile
@@ -189,6 +197,8 @@ impl TryFrom<NonNull<sys::rs2_stream_profile>> for StreamProfile {
                 sys::rs2_is_stream_profile_default(stream_profile_ptr.as_ptr(), &mut err);
             check_rs2_error!(err, StreamConstructionError::CouldNotDetermineIsDefault)?;
 
+            let resolution = video_resolution(stream_profile_ptr);
+
             Ok(StreamProfile {
                 ptr: stream_profile_ptr,
                 stream: Rs2StreamKind::from_i32(stream.assume_init() as i32).unwrap(),
@@ -196,6 +206,7 @@ impl TryFrom<NonNull<sys::rs2_stream_profile>> for StreamProfile {
                 index: index.assume_init() as usize,
                 unique_id: unique_id.assume_init(),
                 framerate: framerate.assume_init(),
+                resolution,
                 is_default: is_default != 0,
                 should_drop: false,
             })
@@ -203,6 +214,81 @@ impl TryFrom<NonNull<sys::rs2_stream_profile>> for StreamProfile {
     }
 }
 
+/// A view of a [`StreamProfile`] that is known to describe a video stream.
+///
+/// Unlike other stream profiles, video stream profiles have a resolution. This type is obtained
+/// via [`StreamProfile::as_video`], so that the resolution is only available on profiles which
+/// have one.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoStreamProfile<'a> {
+    /// The underlying stream profile.
+    profile: &'a StreamProfile,
+    /// The width of the stream in pixels.
+    width: usize,
+    /// The height of the stream in pixels.
+    height: usize,
+}
+
+impl<'a> VideoStreamProfile<'a> {
+    /// Get the underlying stream profile.
+    pub fn profile(&self) -> &'a StreamProfile {
+        self.profile
+    }
+
+    /// Get the width of the stream in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the stream in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the video intrinsics of the stream.
+    ///
+    /// See [`StreamProfile::intrinsics`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DataError::CouldNotGetIntrinsics`] if the intrinsics cannot be obtained.
+    pub fn intrinsics(&self) -> Result<Rs2Intrinsics, DataError> {
+        self.profile.intrinsics()
+    }
+}
+
+/// Stream profiles compare equal if they describe the same stream, i.e. if their stream kind,
+/// format, index, unique identifier, framerate and (for video streams) resolution are equal.
+///
+/// Whether the stream is a default stream and which underlying pointer the profile was obtained
+/// from are not taken into account.
+impl PartialEq for StreamProfile {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for StreamProfile {}
+
+impl Hash for StreamProfile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+/// Formats the profile for presentation to a user, e.g. `Depth Z16 1280x720@30 idx0`.
+///
+/// The resolution is only included for video streams, e.g. `Accel MotionXyz32f @200 idx0`.
+impl Display for StreamProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?} ", self.stream, self.format)?;
+        if let Some((width, height)) = self.resolution {
+            write!(f, "{}x{}", width, height)?;
+        }
+        write!(f, "@{} idx{}", self.framerate, self.index)
+    }
+}
+
 impl Drop for StreamProfile {
     fn drop(&mut self) {
         unsafe {
@@ -238,40 +324,125 @@ impl StreamProfile {
             let profile_ptr = sys::rs2_get_stream_profile(profiles.as_ptr(), index, &mut err);
             check_rs2_error!(err, StreamConstructionError::CouldNotGetProfileFromList)?;
 
-            let mut stream = MaybeUninit::uninit();
-            let mut format = MaybeUninit::uninit();
-            let mut index = MaybeUninit::uninit();
-            let mut unique_id = MaybeUninit::uninit();
-            let mut framerate = MaybeUninit::uninit();
-
-            sys::rs2_get_stream_profile_data(
-                profile_ptr,
-                stream.as_mut_ptr(),
-                format.as_mut_ptr(),
-                index.as_mut_ptr(),
-                unique_id.as_mut_ptr(),
-                framerate.as_mut_ptr(),
-                &mut err,
-            );
-            check_rs2_error!(err, StreamConstructionError::CouldNotRetrieveStreamData)?;
+            Self::clone_from_raw(profile_ptr)
+        }
+    }
 
-            let profile_ptr = sys::rs2_clone_stream_profile(
-                profile_ptr,
-                stream.assume_init(),
-                index.assume_init(),
-                format.assume_init(),
-                &mut err,
-            );
-            check_rs2_error!(err, StreamConstructionError::CouldNotCloneProfile)?;
+    /// Create an owned copy of the stream profile, which does not depend on the lifetime of the
+    /// object (e.g. the frame) this profile was obtained from.
+    ///
+    /// The copy is made with `rs2_clone_stream_profile`, so it is a new stream profile as far as
+    /// librealsense2 is concerned: it describes the same stream kind, format, index, framerate and
+    /// resolution, and provides the same intrinsics, but is assigned its own
+    /// [unique identifier](StreamProfile::unique_id).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StreamConstructionError::CouldNotCloneProfile`] if the profile cannot be cloned,
+    /// and any other [`StreamConstructionError`] if the data of the copy cannot be retrieved.
+    pub fn try_clone(&self) -> Result<Self, StreamConstructionError> {
+        unsafe { Self::clone_from_raw(self.ptr.as_ptr()) }
+    }
 
-            let nonnull_profile_ptr = NonNull::new(profile_ptr).unwrap();
-            let mut stream_profile = Self::try_from(nonnull_profile_ptr)?;
-            stream_profile.should_drop = true;
+    /// Construct an owned stream profile by cloning the profile behind `profile_ptr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StreamConstructionError`] if the profile cannot be cloned or if the data of the
+    /// clone cannot be retrieved.
+    ///
+    /// # Safety
+    ///
+    /// `profile_ptr` must point to a valid stream profile.
+    unsafe fn clone_from_raw(
+        profile_ptr: *const sys::rs2_stream_profile,
+    ) -> Result<Self, StreamConstructionError> {
+        let mut err = std::ptr::null_mut::<sys::rs2_error>();
+        let mut stream = MaybeUninit::uninit();
+        let mut format = MaybeUninit::uninit();
+        let mut index = MaybeUninit::uninit();
+        let mut unique_id = MaybeUninit::uninit();
+        let mut framerate = MaybeUninit::uninit();
+
+        sys::rs2_get_stream_profile_data(
+            profile_ptr,
+            stream.as_mut_ptr(),
+            format.as_mut_ptr(),
+            index.as_mut_ptr(),
+            unique_id.as_mut_ptr(),
+            framerate.as_mut_ptr(),
+            &mut err,
+        );
+        check_rs2_error!(err, StreamConstructionError::CouldNotRetrieveStreamData)?;
+
+        let profile_ptr = sys::rs2_clone_stream_profile(
+            profile_ptr,
+            stream.assume_init(),
+            index.assume_init(),
+            format.assume_init(),
+            &mut err,
+        );
+        check_rs2_error!(err, StreamConstructionError::CouldNotCloneProfile)?;
+
+        let nonnull_profile_ptr = NonNull::new(profile_ptr).unwrap();
+        let mut stream_profile = Self::try_from(nonnull_profile_ptr)?;
+        stream_profile.should_drop = true;
+
+        Ok(stream_profile)
+    }
 
-            Ok(stream_profile)
+    /// Make a copy of the stream profile that refers to the same underlying librealsense2 profile.
+    ///
+    /// The copy never deletes the underlying profile, so it must not outlive whatever owns the
+    /// profile, e.g. the frame (or a reference to the frame) that the profile belongs to.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            ptr: self.ptr,
+            stream: self.stream,
+            format: self.format,
+            index: self.index,
+            unique_id: self.unique_id,
+            framerate: self.framerate,
+            resolution: self.resolution,
+            is_default: self.is_default,
+            should_drop: false,
         }
     }
 
+    /// Get the fields that identify the stream, for use in equality and hashing.
+    fn identity(
+        &self,
+    ) -> (
+        Rs2StreamKind,
+        Rs2Format,
+        usize,
+        i32,
+        i32,
+        Option<(usize, usize)>,
+    ) {
+        (
+            self.stream,
+            self.format,
+            self.index,
+            self.unique_id,
+            self.framerate,
+            self.resolution,
+        )
+    }
+
+    /// View this profile as a video stream profile.
+    ///
+    /// Returns `None` if the profile does not describe a video stream (i.e. the profile is not
+    /// extendable to [`Rs2Extension::VideoProfile`]), e.g. for motion streams.
+    pub fn as_video(&self) -> Option<VideoStreamProfile<'_>> {
+        let (width, height) = self.resolution?;
+        Some(VideoStreamProfile {
+            profile: self,
+            width,
+            height,
+        })
+    }
+
     /// Predicate for whether or not the stream is a default stream.
     #[inline]
     pub fn is_default(&self) -> bool {
@@ -437,3 +608,36 @@ impl StreamProfile {
         }
     }
 }
+
+/// Get the (width, height) of the stream behind `profile_ptr`.
+///
+/// Returns `None` if the stream is not a video stream, or if the resolution cannot be obtained.
+unsafe fn video_resolution(
+    profile_ptr: NonNull<sys::rs2_stream_profile>,
+) -> Option<(usize, usize)> {
+    let mut err = std::ptr::null_mut::<sys::rs2_error>();
+
+    let is_video = sys::rs2_stream_profile_is(
+        profile_ptr.as_ptr(),
+        #[allow(clippy::useless_conversion)]
+        (Rs2Extension::VideoProfile as i32).try_into().unwrap(),
+        &mut err,
+    );
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return None;
+    }
+    if is_video == 0 {
+        return None;
+    }
+
+    let mut width = 0;
+    let mut height = 0;
+    sys::rs2_get_video_stream_resolution(profile_ptr.as_ptr(), &mut width, &mut height, &mut err);
+    if err.as_ref().is_some() {
+        sys::rs2_free_error(err);
+        return None;
+    }
+
+    Some((width as usize, height as usize))
+}
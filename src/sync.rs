@@ -0,0 +1,145 @@
+//! Types for synchronizing framesets across multiple devices.
+//!
+//! librealsense2 synchronizes the streams of a single device, but has no notion of
+//! synchronization across devices. The [`MultiPipeline`] type waits on several independent
+//! pipelines and aligns their framesets by the host time at which they arrived, which is good
+//! enough for roughly time-aligned multi-view capture.
+//!
+//! For tighter synchronization, hardware sync between the devices should be configured instead
+//! (see [`Rs2Option::InterCamSyncMode`](crate::kind::Rs2Option::InterCamSyncMode)).
+
+use crate::{frame::CompositeFrame, kind::Rs2FrameMetadata, pipeline::ActivePipeline};
+use anyhow::Result;
+use std::time::Duration;
+use thiserror::Error;
+
+/// The maximum number of framesets re-fetched while trying to synchronize the pipelines.
+const MAX_SYNC_ATTEMPTS: usize = 100;
+
+/// Type describing errors that can occur when synchronizing framesets across pipelines.
+#[derive(Error, Debug)]
+pub enum SyncError {
+    /// A frameset did not carry a time of arrival, so it cannot be synchronized.
+    #[error("Frameset from pipeline {0} does not carry a time of arrival.")]
+    MissingTimeOfArrival(usize),
+    /// The framesets could not be brought within the tolerance.
+    #[error("Could not synchronize framesets within tolerance after {0} attempts.")]
+    CouldNotSynchronize(usize),
+}
+
+/// Holds several active pipelines and produces roughly time-aligned framesets from them.
+#[derive(Debug)]
+pub struct MultiPipeline {
+    /// The pipelines to synchronize.
+    pipelines: Vec<ActivePipeline>,
+}
+
+impl MultiPipeline {
+    /// Construct a new multi-pipeline from a set of active pipelines.
+    pub fn new(pipelines: Vec<ActivePipeline>) -> Self {
+        Self { pipelines }
+    }
+
+    /// Get the pipelines being synchronized.
+    pub fn pipelines(&self) -> &[ActivePipeline] {
+        &self.pipelines
+    }
+
+    /// Take back ownership of the pipelines, e.g. to stop them.
+    pub fn into_pipelines(self) -> Vec<ActivePipeline> {
+        self.pipelines
+    }
+
+    /// Wait for a frameset from every pipeline, such that all framesets arrived within
+    /// `tolerance` of each other.
+    ///
+    /// A frameset is first waited for on each pipeline. As long as the
+    /// [time of arrival](Rs2FrameMetadata::TimeOfArrival) of the framesets differs by more than
+    /// `tolerance`, the oldest frameset is discarded and replaced with the next frameset from its
+    /// pipeline. The returned framesets are in the same order as the pipelines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SyncError::MissingTimeOfArrival`] if a frameset does not carry a time of arrival.
+    ///
+    /// Returns [`SyncError::CouldNotSynchronize`] if the framesets could not be brought within
+    /// `tolerance` of each other after a bounded number of attempts, e.g. if the tolerance is
+    /// smaller than the spread between the frame periods of the devices.
+    ///
+    /// Returns any [`FrameWaitError`](crate::pipeline::FrameWaitError) that occurs while waiting
+    /// for a frameset.
+    pub fn wait_for_synced(&mut self, tolerance: Duration) -> Result<Vec<CompositeFrame>> {
+        let tolerance_ms = tolerance.as_millis() as i64;
+
+        let mut framesets = Vec::with_capacity(self.pipelines.len());
+        let mut arrivals = Vec::with_capacity(self.pipelines.len());
+        for (i, pipeline) in self.pipelines.iter_mut().enumerate() {
+            let frameset = pipeline.wait(None)?;
+            arrivals.push(time_of_arrival(&frameset, i)?);
+            framesets.push(frameset);
+        }
+
+        for _ in 0..MAX_SYNC_ATTEMPTS {
+            let i = match laggard(&arrivals, tolerance_ms) {
+                Some(i) => i,
+                None => return Ok(framesets),
+            };
+
+            let frameset = self.pipelines[i].wait(None)?;
+            arrivals[i] = time_of_arrival(&frameset, i)?;
+            framesets[i] = frameset;
+        }
+
+        match laggard(&arrivals, tolerance_ms) {
+            Some(_) => Err(SyncError::CouldNotSynchronize(MAX_SYNC_ATTEMPTS).into()),
+            None => Ok(framesets),
+        }
+    }
+}
+
+/// Get the time of arrival of the frameset received from pipeline `index`.
+///
+/// # Errors
+///
+/// Returns [`SyncError::MissingTimeOfArrival`] if the frameset does not carry a time of arrival.
+fn time_of_arrival(frameset: &CompositeFrame, index: usize) -> Result<i64, SyncError> {
+    frameset
+        .metadata(Rs2FrameMetadata::TimeOfArrival)
+        .ok_or(SyncError::MissingTimeOfArrival(index))
+}
+
+/// Find the index of the earliest arrival, if the arrivals are not within `tolerance_ms` of each
+/// other.
+///
+/// Returns `None` if the arrivals are within tolerance.
+fn laggard(arrivals: &[i64], tolerance_ms: i64) -> Option<usize> {
+    let latest = *arrivals.iter().max()?;
+    let (index, earliest) = arrivals
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &arrival)| arrival)?;
+
+    if latest - earliest > tolerance_ms {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrivals_within_tolerance_are_synced() {
+        assert_eq!(laggard(&[], 5), None);
+        assert_eq!(laggard(&[100], 0), None);
+        assert_eq!(laggard(&[100, 103, 105], 5), None);
+    }
+
+    #[test]
+    fn earliest_arrival_is_the_laggard() {
+        assert_eq!(laggard(&[100, 133], 5), Some(0));
+        assert_eq!(laggard(&[140, 100, 133], 10), Some(1));
+    }
+}
@@ -3,7 +3,7 @@
 #![cfg(feature = "test-single-device")]
 
 use realsense_rust::{
-    config::Config,
+    config::{Config, ConfigurationError},
     context::Context,
     kind::{Rs2Format, Rs2ProductLine, Rs2StreamKind},
     pipeline::InactivePipeline,
@@ -35,6 +35,29 @@ fn can_resolve_all_streams_always() {
     assert!(pipeline.can_resolve(&config));
 }
 
+#[test]
+fn cannot_enable_incompatible_format() {
+    let mut config = Config::new();
+
+    let result = config.disable_all_streams().unwrap().enable_stream(
+        Rs2StreamKind::Depth,
+        Some(0),
+        0,
+        0,
+        // Depth should not be able to provide motion data!
+        Rs2Format::MotionXyz32F,
+        100,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ConfigurationError::IncompatibleFormat(
+            Rs2StreamKind::Depth,
+            Rs2Format::MotionXyz32F
+        ))
+    ));
+}
+
 #[test]
 fn cannot_resolve_bad_config() {
     let context = Context::new().unwrap();
@@ -48,9 +71,9 @@ fn cannot_resolve_bad_config() {
             Some(0),
             0,
             0,
-            // Depth should not be able to provide motion data!
-            Rs2Format::MotionXyz32F,
-            100,
+            Rs2Format::Z16,
+            // No depth sensor streams this fast!
+            1000,
         )
         .unwrap();
 
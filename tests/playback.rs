@@ -0,0 +1,86 @@
+//! Tests for evaluating sensors of a played back recording.
+//!
+//! These run without any device connected, against the ROSbag file given by the
+//! `REALSENSE_TEST_BAG` environment variable.
+
+#![cfg(feature = "test-playback")]
+
+use num_traits::FromPrimitive;
+use realsense_rust::{
+    context::Context,
+    device::Device,
+    kind::{OptionSetError, Rs2Extension, Rs2Option},
+};
+
+/// Add the recording given by `REALSENSE_TEST_BAG` to a new context.
+fn playback_device() -> (Context, Device) {
+    let bag_file = std::env::var("REALSENSE_TEST_BAG")
+        .expect("REALSENSE_TEST_BAG must be set to the path of a recording to run these tests");
+
+    let context = Context::new().unwrap();
+    let device = context.add_device_from_file(bag_file).unwrap();
+    (context, device)
+}
+
+#[test]
+fn playback_device_is_playback() {
+    let (_context, device) = playback_device();
+
+    assert!(device.is_extendable_to(Rs2Extension::Playback));
+}
+
+#[test]
+fn playback_sensors_have_stream_profiles() {
+    let (_context, device) = playback_device();
+    let sensors = device.try_sensors().unwrap();
+
+    assert!(!sensors.is_empty());
+    for sensor in sensors {
+        assert!(!sensor.stream_profiles().is_empty());
+    }
+}
+
+#[test]
+fn playback_sensor_indices_are_stable() {
+    let (_context, device) = playback_device();
+    let sensors = device.try_sensors().unwrap();
+
+    for (index, sensor) in sensors.iter().enumerate() {
+        let name = sensor.name().unwrap();
+        assert_eq!(device.sensor_index_by_name(&name), Some(index));
+    }
+
+    // Enumerating again yields the sensors in the same order.
+    let names = sensors.iter().map(|s| s.name()).collect::<Vec<_>>();
+    let names_again = device
+        .try_sensors()
+        .unwrap()
+        .iter()
+        .map(|s| s.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, names_again);
+}
+
+#[test]
+fn playback_sensors_report_recorded_options() {
+    let (_context, device) = playback_device();
+
+    for mut sensor in device.sensors() {
+        let options = (0..realsense_sys::rs2_option_RS2_OPTION_COUNT as i32)
+            .filter_map(Rs2Option::from_i32)
+            .filter(|&option| sensor.supports_option(option))
+            .collect::<Vec<_>>();
+
+        for option in options {
+            let value = sensor.get_option(option);
+            assert!(value.is_some(), "{:?} has no recorded value", option);
+
+            // The recorded values are snapshots, which cannot be changed during playback.
+            assert!(sensor.is_option_read_only(option));
+            assert!(matches!(
+                sensor.set_option(option, value.unwrap()),
+                Err(OptionSetError::OptionIsReadOnly)
+            ));
+        }
+    }
+}